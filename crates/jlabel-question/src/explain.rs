@@ -0,0 +1,123 @@
+//! Explanation of question matching
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{Display, Formatter, Result},
+    ops::Range,
+};
+
+use jlabel::Label;
+
+use crate::{
+    AllQuestion, Question,
+    position::{AllPosition, BooleanPosition, CategoryPosition, Position},
+};
+
+/// The report of why an [`AllQuestion`] did or did not match a label.
+///
+/// This is the result of [`AllQuestion::explain`].
+///
+/// Values are written in the same way as they appear in the full-context label,
+/// e.g. the logic of E5/G5 is *not* inverted here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// The position the question is about.
+    pub position: AllPosition,
+    /// The value at the position in the label, or `None` if it is `xx`.
+    pub value: Option<String>,
+    /// The range or set of values the question expects, or `None` if it expects `xx`.
+    pub expected: Option<String>,
+    /// Whether the question matched the label.
+    pub is_match: bool,
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{} = {}, expected {}: {}",
+            self.position,
+            self.value.as_deref().unwrap_or("xx"),
+            self.expected.as_deref().unwrap_or("xx"),
+            if self.is_match {
+                "matched"
+            } else {
+                "not matched"
+            }
+        )
+    }
+}
+
+impl AllQuestion {
+    /// Checks if the full-context label matches the question,
+    /// and reports the value and the expected range involved in the check.
+    pub fn explain(&self, label: &Label) -> Explanation {
+        match self {
+            Self::Phone(q) => q.explain_with(label, AllPosition::Phone, String::clone, |r| set(r)),
+            Self::SignedRange(q) => {
+                q.explain_with(label, AllPosition::SignedRange, i8::to_string, range)
+            }
+            Self::UnsignedRange(q) => {
+                q.explain_with(label, AllPosition::UnsignedRange, u8::to_string, range)
+            }
+            Self::Boolean(q) => {
+                // E5/G5's logics are inverted
+                let inverted = matches!(q.position, BooleanPosition::E5 | BooleanPosition::G5);
+                let digit = |value: &bool| u8::from(*value != inverted).to_string();
+                q.explain_with(label, AllPosition::Boolean, digit, digit)
+            }
+            Self::Category(q) => {
+                // B1/C1/D1 are zero-padded to two digits
+                let padded = matches!(
+                    q.position,
+                    CategoryPosition::B1 | CategoryPosition::C1 | CategoryPosition::D1
+                );
+                let digits = |value: &u8| {
+                    if padded {
+                        format!("{value:02}")
+                    } else {
+                        value.to_string()
+                    }
+                };
+                q.explain_with(label, AllPosition::Category, digits, |r| {
+                    set(&r.iter().map(digits).collect::<Vec<_>>())
+                })
+            }
+            Self::Undefined(q) => q.explain_with(
+                label,
+                AllPosition::Undefined,
+                |()| "xx".to_string(),
+                |()| "not xx".to_string(),
+            ),
+        }
+    }
+}
+
+impl<P: Position + Copy> Question<P> {
+    fn explain_with(
+        &self,
+        label: &Label,
+        position: impl FnOnce(P) -> AllPosition,
+        value: impl FnOnce(&P::Target) -> String,
+        expected: impl FnOnce(&P::Range) -> String,
+    ) -> Explanation {
+        Explanation {
+            position: position(self.position),
+            value: self.position.get(label).map(value),
+            expected: self.range.as_ref().map(expected),
+            is_match: self.test(label),
+        }
+    }
+}
+
+fn set(values: &[String]) -> String {
+    format!("{{{}}}", values.join(", "))
+}
+
+fn range<Idx: Display>(range: &Range<Idx>) -> String {
+    format!("{}..{}", range.start, range.end)
+}
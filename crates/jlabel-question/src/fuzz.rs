@@ -0,0 +1,190 @@
+//! Generators of question patterns for fuzzing and property testing
+//!
+//! Requires `arbitrary` or `proptest` feature.
+//! [`QuestionPatterns`] implements `Arbitrary` of the enabled crates,
+//! and the patterns generated are always successfully parsed as [`crate::AllQuestion`].
+//!
+//! Labels are generated by `Arbitrary` implementations of [`jlabel::Label`],
+//! which are enabled by the same features.
+
+use crate::{
+    position::{AllPosition, CategoryPosition},
+    raw,
+};
+
+/// Question patterns about a single position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuestionPatterns {
+    /// The position the patterns are about.
+    pub position: AllPosition,
+    /// The patterns.
+    pub patterns: Vec<String>,
+}
+
+impl QuestionPatterns {
+    /// Wraps the values of the field at the position with its context, e.g. `3` into `*/A:3+*`.
+    fn new(position: AllPosition, values: Vec<String>) -> Self {
+        let (before, after) = raw::context(position);
+        let patterns = values
+            .into_iter()
+            .map(|value| {
+                let head = if before.is_empty() { "" } else { "*" };
+                let tail = if after.is_empty() { "" } else { "*" };
+                format!("{head}{before}{value}{after}{tail}")
+            })
+            .collect();
+        Self { position, patterns }
+    }
+}
+
+/// The wildcards for numerical fields, which are parsed into continuous range.
+fn wildcards(position: AllPosition) -> &'static [&'static str] {
+    match position {
+        AllPosition::SignedRange(_) => &["-??", "-?", "?"],
+        _ => &["?"],
+    }
+}
+
+/// The range of numerical literals.
+fn literal_range(position: AllPosition) -> (i16, i16) {
+    match position {
+        AllPosition::SignedRange(_) => (-99, 99),
+        _ => (0, 99),
+    }
+}
+
+/// Whether the categorical field is zero-padded in two digits.
+fn is_padded(position: AllPosition) -> bool {
+    matches!(
+        position,
+        AllPosition::Category(CategoryPosition::B1 | CategoryPosition::C1 | CategoryPosition::D1)
+    )
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary {
+    use std::ops::ControlFlow;
+
+    use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+    use jlabel::arbitrary::phoneme;
+
+    use super::*;
+
+    impl<'a> Arbitrary<'a> for AllPosition {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            u.choose(&raw::POSITIONS).copied()
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for QuestionPatterns {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            let position = AllPosition::arbitrary(u)?;
+            Ok(Self::new(position, values(u, position)?))
+        }
+    }
+
+    fn values(u: &mut Unstructured<'_>, position: AllPosition) -> Result<Vec<String>> {
+        if matches!(position, AllPosition::Undefined(_)) || u.ratio(1, 8)? {
+            return Ok(vec!["xx".to_string()]);
+        }
+        match position {
+            AllPosition::Phone(_) => {
+                let mut phonemes = Vec::new();
+                u.arbitrary_loop(Some(1), Some(3), |u| {
+                    phonemes.push(phoneme(u)?);
+                    Ok(ControlFlow::Continue(()))
+                })?;
+                Ok(phonemes)
+            }
+            AllPosition::SignedRange(_) | AllPosition::UnsignedRange(_) => {
+                if u.ratio(1, 4)? {
+                    let wildcard = u.choose(wildcards(position))?;
+                    return Ok(vec![wildcard.to_string()]);
+                }
+                let (min, max) = literal_range(position);
+                let start = u.int_in_range(min..=max)?;
+                let end = u.int_in_range(start..=max.min(start + 4))?;
+                Ok((start..=end).map(|value| value.to_string()).collect())
+            }
+            AllPosition::Boolean(_) => Ok(vec![u.choose(&["0", "1"])?.to_string()]),
+            AllPosition::Category(_) => {
+                let mut categories = Vec::new();
+                u.arbitrary_loop(Some(1), Some(3), |u| {
+                    categories.push(if is_padded(position) {
+                        format!("{:02}", u.int_in_range(0..=99)?)
+                    } else {
+                        u.int_in_range(0..=9)?.to_string()
+                    });
+                    Ok(ControlFlow::Continue(()))
+                })?;
+                Ok(categories)
+            }
+            AllPosition::Undefined(_) => unreachable!(),
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+mod proptest {
+    use ::proptest::{
+        arbitrary::Arbitrary,
+        collection::vec,
+        prop_oneof,
+        sample::select,
+        strategy::{BoxedStrategy, Just, Strategy},
+    };
+
+    use jlabel::proptest::phoneme;
+
+    use super::*;
+
+    impl Arbitrary for AllPosition {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            select(&raw::POSITIONS[..]).boxed()
+        }
+    }
+
+    impl Arbitrary for QuestionPatterns {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: ()) -> Self::Strategy {
+            select(&raw::POSITIONS[..])
+                .prop_flat_map(|position| {
+                    values(position).prop_map(move |values| Self::new(position, values))
+                })
+                .boxed()
+        }
+    }
+
+    fn values(position: AllPosition) -> BoxedStrategy<Vec<String>> {
+        let values = match position {
+            AllPosition::Phone(_) => vec(phoneme(), 1..=3).boxed(),
+            AllPosition::SignedRange(_) | AllPosition::UnsignedRange(_) => {
+                let (min, max) = literal_range(position);
+                let literals = (min..=max, 0..5i16).prop_map(move |(start, len)| {
+                    (start..=max.min(start + len))
+                        .map(|value| value.to_string())
+                        .collect()
+                });
+                let wildcard = select(wildcards(position)).prop_map(|w| vec![w.to_string()]);
+                prop_oneof![3 => literals, 1 => wildcard].boxed()
+            }
+            AllPosition::Boolean(_) => select(&["0", "1"][..])
+                .prop_map(|value| vec![value.to_string()])
+                .boxed(),
+            AllPosition::Category(_) if is_padded(position) => {
+                vec((0..=99u8).prop_map(|value| format!("{value:02}")), 1..=3).boxed()
+            }
+            AllPosition::Category(_) => {
+                vec((0..=9u8).prop_map(|value| value.to_string()), 1..=3).boxed()
+            }
+            AllPosition::Undefined(_) => return Just(vec!["xx".to_string()]).boxed(),
+        };
+        prop_oneof![7 => values, 1 => Just(vec!["xx".to_string()])].boxed()
+    }
+}
@@ -1,3 +1,4 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
@@ -61,42 +62,47 @@
 //! If you just want to ignore those pattern, you can simply return `false` instead of the result of `test()`.
 //!
 //! If you need to successfully parse pattern which [`AllQuestion`] fails to parse,
-//! [`regex::RegexQuestion`] is the best choice.
+//! [`AnyQuestion`] (requires `regex` feature) tries [`AllQuestion`] first,
+//! and falls back to [`regex::RegexQuestion`] if it fails.
+//! If you would rather not enable `regex` feature,
+//! [`wildcard::WildcardQuestion`] does the same without any dependency.
 //!
 //! ```rust
 //! # #[cfg(feature = "regex")]
 //! # {
-//! use jlabel::Label;
-//! use jlabel_question::{regex::RegexQuestion, AllQuestion, ParseError, QuestionMatcher};
+//! use jlabel_question::{AnyQuestion, QuestionMatcher};
+//!
+//! let question = AnyQuestion::parse(&["*/A:-??+*", "*/A:-?+*"]).unwrap();
+//! assert!(matches!(question, AnyQuestion::Structured(_)));
 //!
-//! enum Pattern {
-//!     AllQustion(AllQuestion),
-//!     Regex(RegexQuestion),
-//! }
-//! impl Pattern {
-//!     fn parse(patterns: &[&str]) -> Result<Self, ParseError> {
-//!         match AllQuestion::parse(patterns) {
-//!             Ok(question) => Ok(Self::AllQustion(question)),
-//!             Err(_) => Ok(Self::Regex(RegexQuestion::parse(patterns)?)),
-//!         }
-//!     }
-//!     fn test(&self, label: &Label) -> bool {
-//!         match self {
-//!             Self::AllQustion(question) => question.test(label),
-//!             Self::Regex(question) => question.test(label),
-//!         }
-//!     }
-//! }
+//! // This has conditions on two positions, so AllQuestion cannot parse it.
+//! let question = AnyQuestion::parse(&["*^k-o+*"]).unwrap();
+//! assert!(matches!(question, AnyQuestion::Regex { .. }));
 //! # }
 //! ```
 
+extern crate alloc;
+
+pub mod explain;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub mod fuzz;
+pub mod lint;
 pub mod parse_position;
 pub mod position;
+mod raw;
+#[cfg(feature = "std")]
+pub mod stats;
 
+#[cfg(feature = "rayon")]
+pub mod parallel;
 #[cfg(feature = "regex")]
 pub mod regex;
+#[cfg(feature = "regex")]
+pub mod verify;
+pub mod wildcard;
 
-use std::num::ParseIntError;
+use alloc::{string::String, vec::Vec};
+use core::num::ParseIntError;
 
 use position::{
     AllPosition, BooleanPosition, CategoryPosition, PhonePosition, Position, SignedRangePosition,
@@ -170,8 +176,17 @@ where
 
     /// Checks if the full-context label matches the question.
     ///
-    /// If you want to `test` on string label, parse it using `Label::from_str()` beforehand.
+    /// If you want to `test` on string label, use [`QuestionMatcher::test_str`] instead.
     fn test(&self, label: &Label) -> bool;
+
+    /// Checks if the full-context label string matches the question.
+    ///
+    /// By default, this parses the label using `Label::from_str()` and then calls `test`,
+    /// returning `false` if the label fails to parse.
+    /// The implementors in this crate override it to avoid parsing the whole label.
+    fn test_str(&self, label: &str) -> bool {
+        label.parse().is_ok_and(|label| self.test(&label))
+    }
 }
 
 /// A main structure representing question.
@@ -234,6 +249,130 @@ impl QuestionMatcher for AllQuestion {
             Self::Undefined(q) => q.test(label),
         }
     }
+    /// Checks if the full-context label string matches the question.
+    ///
+    /// Only the field this question is about is extracted from the string.
+    /// Returns `false` if the field cannot be found or parsed.
+    fn test_str(&self, label: &str) -> bool {
+        let Some(field) = raw::field(label, self.position()) else {
+            return false;
+        };
+        let field = (field != "xx").then_some(field);
+        match self {
+            Self::Phone(q) => match (&q.range, field) {
+                (Some(range), Some(field)) => range.iter().any(|phone| phone == field),
+                (None, None) => true,
+                _ => false,
+            },
+            Self::SignedRange(q) => match field.map(str::parse) {
+                Some(Err(_)) => false,
+                Some(Ok(value)) => q.test_value(Some(&value)),
+                None => q.test_value(None),
+            },
+            Self::UnsignedRange(q) => match field.map(str::parse) {
+                Some(Err(_)) => false,
+                Some(Ok(value)) => q.test_value(Some(&value)),
+                None => q.test_value(None),
+            },
+            Self::Boolean(q) => {
+                // E5/G5's logics are inverted
+                let inverted = matches!(q.position, BooleanPosition::E5 | BooleanPosition::G5);
+                match field {
+                    Some("0") => q.test_value(Some(&inverted)),
+                    Some("1") => q.test_value(Some(&!inverted)),
+                    Some(_) => false,
+                    None => q.test_value(None),
+                }
+            }
+            Self::Category(q) => match field.map(str::parse) {
+                Some(Err(_)) => false,
+                Some(Ok(value)) => q.test_value(Some(&value)),
+                None => q.test_value(None),
+            },
+            Self::Undefined(q) => q.test_value(None),
+        }
+    }
+}
+
+impl AllQuestion {
+    /// The position this question is about.
+    pub fn position(&self) -> AllPosition {
+        match self {
+            Self::Phone(q) => AllPosition::Phone(q.position),
+            Self::SignedRange(q) => AllPosition::SignedRange(q.position),
+            Self::UnsignedRange(q) => AllPosition::UnsignedRange(q.position),
+            Self::Boolean(q) => AllPosition::Boolean(q.position),
+            Self::Category(q) => AllPosition::Category(q.position),
+            Self::Undefined(q) => AllPosition::Undefined(q.position),
+        }
+    }
+}
+
+/// A question parsed as [`AllQuestion`] if possible, and as [`regex::RegexQuestion`] otherwise.
+///
+/// Requires `regex` feature.
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AnyQuestion {
+    /// The patterns were successfully parsed as [`AllQuestion`].
+    Structured(AllQuestion),
+    /// The patterns failed to parse as [`AllQuestion`], and are matched using regex.
+    Regex {
+        /// The question matched using regex.
+        question: regex::RegexQuestion,
+        /// The error [`AllQuestion::parse`] returned for the patterns.
+        ///
+        /// This is `None` if the question was constructed directly or deserialized.
+        #[cfg_attr(feature = "serde", serde(skip))]
+        reason: Option<ParseError>,
+    },
+}
+
+#[cfg(feature = "regex")]
+impl QuestionMatcher for AnyQuestion {
+    fn parse<S: AsRef<str>>(patterns: &[S]) -> Result<Self, ParseError> {
+        match AllQuestion::parse(patterns) {
+            Ok(question) => Ok(Self::Structured(question)),
+            Err(reason) => Ok(Self::Regex {
+                question: regex::RegexQuestion::parse(patterns)?,
+                reason: Some(reason),
+            }),
+        }
+    }
+    fn test(&self, label: &Label) -> bool {
+        match self {
+            Self::Structured(question) => question.test(label),
+            Self::Regex { question, .. } => question.test(label),
+        }
+    }
+    fn test_str(&self, label: &str) -> bool {
+        match self {
+            Self::Structured(question) => question.test_str(label),
+            Self::Regex { question, .. } => question.test_str(label),
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+impl AnyQuestion {
+    /// Returns `true` if the patterns were parsed as [`AllQuestion`],
+    /// i.e. this question is not running on the slow regex path.
+    pub fn is_structured(&self) -> bool {
+        matches!(self, Self::Structured(_))
+    }
+
+    /// The error [`AllQuestion::parse`] returned for the patterns,
+    /// which forced this question to fall back to regex.
+    ///
+    /// Returns `None` if the question is structured,
+    /// or if it was not created by [`AnyQuestion::parse`].
+    pub fn fallback_reason(&self) -> Option<&ParseError> {
+        match self {
+            Self::Structured(_) => None,
+            Self::Regex { reason, .. } => reason.as_ref(),
+        }
+    }
 }
 
 /// An inner structure representing a pair of position and range.
@@ -272,7 +411,11 @@ impl<P: Position> Question<P> {
 
     /// Check if this question matches
     pub fn test(&self, label: &Label) -> bool {
-        match (&self.range, self.position.get(label)) {
+        self.test_value(self.position.get(label))
+    }
+
+    fn test_value(&self, target: Option<&P::Target>) -> bool {
+        match (&self.range, target) {
             (Some(range), Some(target)) => self.position.test(range, target),
             (None, None) => true,
             _ => false,
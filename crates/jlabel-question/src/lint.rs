@@ -0,0 +1,655 @@
+//! Lint of question sets against the full-context label format
+//!
+//! ```rust
+//! use jlabel_question::lint::{EntryKind, QsEntry, lint};
+//!
+//! let entries = [
+//!     QsEntry::parse(r#"QS "C-Hinshi_Meishi" {*/C:02_*,*/C:03_*}"#).unwrap(),
+//!     QsEntry::parse(r#"QS "A1<=-10000" {*/A:-?????+*}"#).unwrap(),
+//!     QsEntry::parse(r#"QS "C-Hinshi_Keiyoushi" {*/C:1_*}"#).unwrap(),
+//! ];
+//! let reports = lint(&entries);
+//!
+//! assert_eq!(reports[0].kind, EntryKind::Structured);
+//! assert!(reports[0].warnings.is_empty());
+//!
+//! assert_eq!(reports[1].kind, EntryKind::Unsatisfiable);
+//!
+//! assert_eq!(reports[2].kind, EntryKind::Unsatisfiable);
+//! assert_eq!(
+//!     reports[2].warnings[0].suggestion.as_deref(),
+//!     Some("*/C:01_*")
+//! );
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{Display, Formatter};
+
+use crate::{
+    AllQuestion, ParseError, QuestionMatcher,
+    parse_position::estimate_position,
+    position::{AllPosition, CategoryPosition, SignedRangePosition},
+};
+
+/// An entry of question set (`QS "name" {pattern,pattern,...}`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QsEntry<'a> {
+    /// The name of the question
+    pub name: &'a str,
+    /// The patterns of the question
+    pub patterns: Vec<&'a str>,
+}
+
+impl<'a> QsEntry<'a> {
+    /// Parses a line of question set file.
+    ///
+    /// Returns `None` if the line is not a `QS` entry.
+    pub fn parse(line: &'a str) -> Option<Self> {
+        let rest = line.trim().strip_prefix("QS")?.trim_start();
+        let rest = rest.strip_prefix('"')?;
+        let (name, rest) = rest.split_once('"')?;
+        let patterns = rest.trim().strip_prefix('{')?.strip_suffix('}')?;
+        Some(Self {
+            name,
+            patterns: patterns.split(',').map(str::trim).collect(),
+        })
+    }
+}
+
+/// How the question entry can be handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    /// The entry can be parsed as [`AllQuestion`].
+    Structured,
+    /// The entry can only be handled by string matching (e.g. [`crate::regex::RegexQuestion`]).
+    RegexOnly,
+    /// The entry never matches any full-context label.
+    Unsatisfiable,
+}
+
+/// The result of lint for a question entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryReport {
+    /// The name of the question
+    pub name: String,
+    /// How the question entry can be handled
+    pub kind: EntryKind,
+    /// The problems found in the entry
+    pub warnings: Vec<Warning>,
+}
+
+/// A problem found in a question entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// The offending pattern, or `None` if the problem is about the entry as a whole.
+    pub pattern: Option<String>,
+    /// The kind of the problem
+    pub kind: WarningKind,
+    /// The pattern that should be used instead, if any.
+    ///
+    /// An empty string means that the pattern should be removed.
+    pub suggestion: Option<String>,
+}
+
+/// Kinds of problems found by lint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WarningKind {
+    /// [`AllQuestion`] cannot handle the pattern or the entry correctly.
+    ///
+    /// Contains the error from [`AllQuestion::parse`], or `None` if [`AllQuestion`] would
+    /// accept the pattern but interpret it differently from string matching.
+    RegexOnly(Option<ParseError>),
+    /// The pattern never matches any full-context label.
+    Unsatisfiable(&'static str),
+    /// The range is not written in the way the field is formatted in full-context label.
+    WrongRange(&'static str),
+    /// The entry matches exactly the same labels as another entry.
+    Duplicate {
+        /// The index of the other entry
+        entry: usize,
+    },
+    /// The entry matches some of the labels another entry matches.
+    Overlap {
+        /// The index of the other entry
+        entry: usize,
+    },
+    /// The pattern appears more than once in the entry.
+    DuplicatePattern,
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if let Some(pattern) = &self.pattern {
+            write!(f, "`{pattern}`: ")?;
+        }
+        match &self.kind {
+            WarningKind::RegexOnly(Some(err)) => write!(f, "only regex can handle this ({err})")?,
+            WarningKind::RegexOnly(None) => {
+                f.write_str("only regex can handle this (AllQuestion interprets it differently)")?
+            }
+            WarningKind::Unsatisfiable(reason) => write!(f, "never matches ({reason})")?,
+            WarningKind::WrongRange(reason) => write!(f, "wrong range ({reason})")?,
+            WarningKind::Duplicate { entry } => write!(f, "duplicate of entry #{entry}")?,
+            WarningKind::Overlap { entry } => write!(f, "overlaps with entry #{entry}")?,
+            WarningKind::DuplicatePattern => f.write_str("duplicate pattern")?,
+        }
+        match self.suggestion.as_deref() {
+            Some("") => f.write_str("; remove it"),
+            Some(suggestion) => write!(f, "; use `{suggestion}` instead"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Classifies every entry of question set, and reports the problems found.
+///
+/// The reports are in the same order as `entries`.
+pub fn lint(entries: &[QsEntry]) -> Vec<EntryReport> {
+    let mut reports = Vec::with_capacity(entries.len());
+    let mut seen: Vec<(EntryKind, Signature)> = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        let mut warnings = Vec::new();
+        let mut satisfiable = Vec::with_capacity(entry.patterns.len());
+        let mut regex_only = false;
+
+        for (i, pattern) in entry.patterns.iter().enumerate() {
+            if entry.patterns[..i].contains(pattern) {
+                warnings.push(Warning {
+                    pattern: Some(pattern.to_string()),
+                    kind: WarningKind::DuplicatePattern,
+                    suggestion: Some(String::new()),
+                });
+                continue;
+            }
+            match check_pattern(pattern) {
+                Check::Ok => satisfiable.push(*pattern),
+                Check::RegexOnly(err) => {
+                    regex_only = true;
+                    satisfiable.push(*pattern);
+                    warnings.push(Warning {
+                        pattern: Some(pattern.to_string()),
+                        kind: WarningKind::RegexOnly(err),
+                        suggestion: None,
+                    });
+                }
+                Check::Unsatisfiable(reason) => warnings.push(Warning {
+                    pattern: Some(pattern.to_string()),
+                    kind: WarningKind::Unsatisfiable(reason),
+                    suggestion: Some(String::new()),
+                }),
+                Check::WrongRange(reason, suggestion) => warnings.push(Warning {
+                    pattern: Some(pattern.to_string()),
+                    kind: WarningKind::WrongRange(reason),
+                    suggestion: Some(suggestion),
+                }),
+            }
+        }
+
+        let (kind, signature) = if satisfiable.is_empty() {
+            (EntryKind::Unsatisfiable, Signature::Never)
+        } else if regex_only {
+            (EntryKind::RegexOnly, Signature::patterns(&satisfiable))
+        } else {
+            match AllQuestion::parse(&satisfiable) {
+                Ok(question) => (EntryKind::Structured, Signature::question(question)),
+                Err(err) => {
+                    warnings.push(Warning {
+                        pattern: None,
+                        kind: WarningKind::RegexOnly(Some(err)),
+                        suggestion: None,
+                    });
+                    (EntryKind::RegexOnly, Signature::patterns(&satisfiable))
+                }
+            }
+        };
+
+        if kind != EntryKind::Unsatisfiable {
+            if let Some(other) = seen
+                .iter()
+                .position(|seen| *seen == (kind, signature.clone()))
+            {
+                warnings.push(Warning {
+                    pattern: None,
+                    kind: WarningKind::Duplicate { entry: other },
+                    suggestion: None,
+                });
+            } else if let Some(other) = seen.iter().position(|(_, seen)| seen.overlaps(&signature))
+            {
+                warnings.push(Warning {
+                    pattern: None,
+                    kind: WarningKind::Overlap { entry: other },
+                    suggestion: None,
+                });
+            }
+        }
+        seen.push((kind, signature));
+
+        reports.push(EntryReport {
+            name: entry.name.to_string(),
+            kind,
+            warnings,
+        });
+    }
+
+    reports
+}
+
+/// The normalized form of entry used for finding duplicates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Signature {
+    Never,
+    Question(AllQuestion),
+    Patterns(Vec<String>),
+}
+
+impl Signature {
+    fn question(mut question: AllQuestion) -> Self {
+        match &mut question {
+            AllQuestion::Phone(q) => {
+                if let Some(range) = &mut q.range {
+                    range.sort_unstable();
+                    range.dedup();
+                }
+            }
+            AllQuestion::Category(q) => {
+                if let Some(range) = &mut q.range {
+                    range.sort_unstable();
+                    range.dedup();
+                }
+            }
+            _ => (),
+        }
+        Self::Question(question)
+    }
+
+    fn patterns(patterns: &[&str]) -> Self {
+        let mut patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+        patterns.sort_unstable();
+        Self::Patterns(patterns)
+    }
+
+    /// Whether some label matches both of the entries.
+    ///
+    /// Only structured entries are compared.
+    fn overlaps(&self, other: &Self) -> bool {
+        let (Self::Question(a), Self::Question(b)) = (self, other) else {
+            return false;
+        };
+        if a.position() != b.position() {
+            return false;
+        }
+        match (a, b) {
+            (AllQuestion::Phone(a), AllQuestion::Phone(b)) => {
+                intersects(&a.range, &b.range, |a, b| a.iter().any(|v| b.contains(v)))
+            }
+            (AllQuestion::SignedRange(a), AllQuestion::SignedRange(b)) => {
+                intersects(&a.range, &b.range, |a, b| {
+                    a.start < b.end && b.start < a.end
+                })
+            }
+            (AllQuestion::UnsignedRange(a), AllQuestion::UnsignedRange(b)) => {
+                intersects(&a.range, &b.range, |a, b| {
+                    a.start < b.end && b.start < a.end
+                })
+            }
+            (AllQuestion::Boolean(a), AllQuestion::Boolean(b)) => a.range == b.range,
+            (AllQuestion::Category(a), AllQuestion::Category(b)) => {
+                intersects(&a.range, &b.range, |a, b| a.iter().any(|v| b.contains(v)))
+            }
+            (AllQuestion::Undefined(_), AllQuestion::Undefined(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Whether the ranges have a value in common, where `None` (`xx`) only matches `None`.
+fn intersects<R>(a: &Option<R>, b: &Option<R>, f: impl FnOnce(&R, &R) -> bool) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => f(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+enum Check {
+    Ok,
+    RegexOnly(Option<ParseError>),
+    Unsatisfiable(&'static str),
+    WrongRange(&'static str, String),
+}
+
+fn check_pattern(pattern: &str) -> Check {
+    let (position, range) = match estimate_position(pattern) {
+        Ok(result) => result,
+        Err(err) => return Check::RegexOnly(Some(ParseError::InvalidPosition(err))),
+    };
+
+    if range == "xx" {
+        return Check::Ok;
+    }
+
+    // The range is a subslice of the pattern
+    let start = range.as_ptr() as usize - pattern.as_ptr() as usize;
+    let replace = |new_range: &str| {
+        format!(
+            "{}{}{}",
+            &pattern[..start],
+            new_range,
+            &pattern[start + range.len()..]
+        )
+    };
+
+    match position {
+        AllPosition::Phone(_) => {
+            if range.contains('?') {
+                // AllQuestion compares phones literally
+                Check::RegexOnly(None)
+            } else {
+                Check::Ok
+            }
+        }
+        AllPosition::SignedRange(SignedRangePosition::A1) => {
+            let (minus, digits) = match range.strip_prefix('-') {
+                Some(digits) => ("-", digits),
+                None => ("", range),
+            };
+            check_number(digits, 127 + minus.len() as u16, false)
+                .map_suggestion(|digits| replace(&format!("{minus}{digits}")))
+                .or_else(|| {
+                    if minus == "-" && digits.ends_with('?') && digits != "?" && digits != "??" {
+                        // e.g. `-1?` cannot be represented as a continuous range by AllQuestion
+                        return Some(Check::RegexOnly(None));
+                    }
+                    None
+                })
+        }
+        AllPosition::UnsignedRange(_) => {
+            if range.starts_with('-') {
+                return Check::Unsatisfiable("negative value in unsigned field");
+            }
+            check_number(range, 255, false).map_suggestion(replace)
+        }
+        AllPosition::Category(position) => {
+            let padded = matches!(
+                position,
+                CategoryPosition::B1 | CategoryPosition::C1 | CategoryPosition::D1
+            );
+            if range.contains('?') {
+                return match check_number(range, 255, padded) {
+                    Check::Ok => Check::RegexOnly(Some(ParseError::FailLiteral(
+                        range.parse::<u8>().unwrap_err(),
+                    ))),
+                    other => other.map_suggestion(replace),
+                };
+            }
+            check_number(range, 255, padded).map_suggestion(replace)
+        }
+        AllPosition::Boolean(_) => match range {
+            "0" | "1" => Check::Ok,
+            _ => Check::Unsatisfiable("boolean field is either 0 or 1"),
+        },
+        AllPosition::Undefined(_) => Check::Unsatisfiable("this field is always xx"),
+    }
+}
+
+/// Checks the digits (which may contain `?`) against numerical field
+/// formatted in plain decimal (or zero-padded to two digits if `padded`).
+fn check_number(digits: &str, max: u16, padded: bool) -> Check {
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit() || b == b'?') {
+        return Check::Unsatisfiable("non-numerical value in numerical field");
+    }
+    if digits.len() > 3 {
+        return Check::Unsatisfiable("too many digits");
+    }
+    let min_value: u16 = digits.replace('?', "0").parse().unwrap();
+    if min_value > max {
+        return Check::Unsatisfiable("value out of range");
+    }
+
+    let min_len = if padded { 2 } else { 1 };
+    if digits.len() < min_len {
+        return Check::WrongRange(
+            "this field is zero-padded to two digits",
+            format!("0{digits}"),
+        );
+    }
+    if digits.len() > min_len && digits.starts_with('0') {
+        let stripped = digits.trim_start_matches('0');
+        return Check::WrongRange(
+            "this field is not zero-padded",
+            format!("{stripped:0>min_len$}"),
+        );
+    }
+
+    Check::Ok
+}
+
+impl Check {
+    fn map_suggestion(self, f: impl FnOnce(&str) -> String) -> Self {
+        match self {
+            Self::WrongRange(reason, suggestion) => Self::WrongRange(reason, f(&suggestion)),
+            other => other,
+        }
+    }
+
+    fn or_else(self, f: impl FnOnce() -> Option<Self>) -> Self {
+        match self {
+            Self::Ok => f().unwrap_or(Self::Ok),
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint_one(patterns: &[&str]) -> EntryReport {
+        let mut reports = lint(&[QsEntry {
+            name: "Q",
+            patterns: patterns.to_vec(),
+        }]);
+        reports.pop().unwrap()
+    }
+
+    #[test]
+    fn parse_entry() {
+        assert_eq!(
+            QsEntry::parse(r#"QS "LL-Phone_Muon"     {sil^*,pau^*}"#),
+            Some(QsEntry {
+                name: "LL-Phone_Muon",
+                patterns: vec!["sil^*", "pau^*"],
+            })
+        );
+        assert_eq!(QsEntry::parse(""), None);
+        assert_eq!(QsEntry::parse(r#"QS "Q" sil^*"#), None);
+    }
+
+    #[test]
+    fn structured() {
+        let report = lint_one(&["*/A:-??+*", "*/A:-?+*"]);
+        assert_eq!(report.kind, EntryKind::Structured);
+        assert_eq!(report.warnings, []);
+    }
+
+    #[test]
+    fn regex_only() {
+        let report = lint_one(&["*/A:-1+*", "*/B:01-*"]);
+        assert_eq!(report.kind, EntryKind::RegexOnly);
+        assert_eq!(
+            report.warnings,
+            [Warning {
+                pattern: None,
+                kind: WarningKind::RegexOnly(Some(ParseError::PositionMismatch)),
+                suggestion: None,
+            }]
+        );
+
+        let report = lint_one(&["*^?-*"]);
+        assert_eq!(report.kind, EntryKind::RegexOnly);
+        assert_eq!(report.warnings[0].kind, WarningKind::RegexOnly(None));
+
+        let report = lint_one(&["*/A:-1?+*"]);
+        assert_eq!(report.kind, EntryKind::RegexOnly);
+        assert_eq!(report.warnings[0].kind, WarningKind::RegexOnly(None));
+
+        let report = lint_one(&["*/C:0?_*"]);
+        assert_eq!(report.kind, EntryKind::RegexOnly);
+        assert!(matches!(
+            report.warnings[0].kind,
+            WarningKind::RegexOnly(Some(ParseError::FailLiteral(_)))
+        ));
+
+        let report = lint_one(&["*/A:*"]);
+        assert_eq!(report.kind, EntryKind::RegexOnly);
+    }
+
+    #[test]
+    fn unsatisfiable() {
+        let report = lint_one(&["*/A:-?????+*"]);
+        assert_eq!(report.kind, EntryKind::Unsatisfiable);
+        assert_eq!(
+            report.warnings[0].to_string(),
+            "`*/A:-?????+*`: never matches (too many digits); remove it"
+        );
+
+        for pattern in [
+            "*/F:-1_*",
+            "*#2_*",
+            "*_1@*",
+            "*+x+*",
+            "*/K:300+*",
+            "*/C:?_*",
+        ] {
+            assert_eq!(lint_one(&[pattern]).kind, EntryKind::Unsatisfiable);
+        }
+
+        let report = lint_one(&["*/A:-?????+*", "*/A:-??+*"]);
+        assert_eq!(report.kind, EntryKind::Structured);
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn wrong_range() {
+        let report = lint_one(&["*/C:1_*"]);
+        assert_eq!(report.kind, EntryKind::Unsatisfiable);
+        assert_eq!(
+            report.warnings[0].to_string(),
+            "`*/C:1_*`: wrong range (this field is zero-padded to two digits); use `*/C:01_*` instead"
+        );
+
+        let report = lint_one(&["*+01+*", "*+2+*"]);
+        assert_eq!(report.kind, EntryKind::Structured);
+        assert_eq!(report.warnings[0].suggestion.as_deref(), Some("*+1+*"));
+
+        let report = lint_one(&["*/A:-03+*"]);
+        assert_eq!(report.warnings[0].suggestion.as_deref(), Some("*/A:-3+*"));
+
+        let report = lint_one(&["*/D:?+*"]);
+        assert_eq!(report.warnings[0].suggestion.as_deref(), Some("*/D:0?+*"));
+
+        let report = lint_one(&["*-01_*"]);
+        assert_eq!(report.warnings[0].suggestion.as_deref(), Some("*-1_*"));
+    }
+
+    #[test]
+    fn duplicate() {
+        let reports = lint(&[
+            QsEntry {
+                name: "A",
+                patterns: vec!["a^*", "i^*"],
+            },
+            QsEntry {
+                name: "B",
+                patterns: vec!["i^*", "a^*", "i^*"],
+            },
+            QsEntry {
+                name: "C",
+                patterns: vec!["*/A:-1+*", "*/A:-2+*"],
+            },
+            QsEntry {
+                name: "D",
+                patterns: vec!["*/A:-2+*", "*/A:-1+*"],
+            },
+        ]);
+        assert_eq!(reports[0].warnings, []);
+        assert_eq!(
+            reports[1].warnings,
+            [
+                Warning {
+                    pattern: Some("i^*".to_string()),
+                    kind: WarningKind::DuplicatePattern,
+                    suggestion: Some(String::new()),
+                },
+                Warning {
+                    pattern: None,
+                    kind: WarningKind::Duplicate { entry: 0 },
+                    suggestion: None,
+                },
+            ]
+        );
+        assert_eq!(reports[2].warnings, []);
+        assert_eq!(
+            reports[3].warnings,
+            [Warning {
+                pattern: None,
+                kind: WarningKind::Duplicate { entry: 2 },
+                suggestion: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn overlap() {
+        let entry = |patterns: &[&'static str]| QsEntry {
+            name: "Q",
+            patterns: patterns.to_vec(),
+        };
+        let reports = lint(&[
+            entry(&["*^a-*", "*^i-*"]),
+            entry(&["*^i-*", "*^u-*"]),
+            entry(&["*^u-*"]),
+            entry(&["*^xx-*"]),
+            entry(&["*-a+*"]),
+            entry(&["*/A:-??+*", "*/A:-?+*"]),
+            entry(&["*/A:-1+*"]),
+            entry(&["*/A:0+*"]),
+            entry(&["*/C:01_*", "*/C:02_*"]),
+            entry(&["*/C:02_*", "*/C:03_*"]),
+        ]);
+        let overlaps: Vec<_> = reports
+            .iter()
+            .map(|report| match report.warnings[..] {
+                [] => None,
+                [
+                    Warning {
+                        kind: WarningKind::Overlap { entry },
+                        ..
+                    },
+                ] => Some(entry),
+                _ => panic!("{:?}", report.warnings),
+            })
+            .collect();
+        assert_eq!(
+            overlaps,
+            [
+                None,
+                Some(0),
+                Some(1),
+                None,
+                None,
+                None,
+                Some(5),
+                None,
+                None,
+                Some(8)
+            ]
+        );
+        assert_eq!(reports[1].warnings[0].to_string(), "overlaps with entry #0");
+    }
+}
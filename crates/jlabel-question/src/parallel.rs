@@ -0,0 +1,63 @@
+//! Parallel evaluation of question sets with [`rayon`]
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::Label;
+//! use jlabel_question::{AllQuestion, QuestionMatcher, parallel::answers};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let questions = [
+//!     ("C-Phone_k", AllQuestion::parse(&["*-k+*"])?),
+//!     ("C-Phone_o", AllQuestion::parse(&["*-o+*"])?),
+//! ];
+//! let labels: Vec<Label> = [
+//!     "xx^sil-k+o=N/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5",
+//!     "sil^k-o+N=n/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5",
+//! ]
+//! .iter()
+//! .map(|s| s.parse())
+//! .collect::<Result<_, _>>()?;
+//!
+//! let sequences = [&labels[..1], &labels[1..]];
+//! assert_eq!(
+//!     answers(&questions, &sequences),
+//!     [vec![vec![true, false]], vec![vec![false, true]]]
+//! );
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! See also [`crate::stats::QuestionStats::collect_par`].
+
+use jlabel::Label;
+use rayon::prelude::*;
+
+use crate::QuestionMatcher;
+
+/// Tests every question against every label of the sequences in parallel.
+///
+/// The result is indexed by the sequence, the label, and the question in this order,
+/// which is the same as the order of the arguments.
+pub fn answers<N, Q, S>(questions: &[(N, Q)], sequences: &[S]) -> Vec<Vec<Vec<bool>>>
+where
+    N: Sync,
+    Q: QuestionMatcher + Sync,
+    S: AsRef<[Label]> + Sync,
+{
+    sequences
+        .par_iter()
+        .map(|sequence| {
+            sequence
+                .as_ref()
+                .iter()
+                .map(|label| {
+                    questions
+                        .iter()
+                        .map(|(_, question)| question.test(label))
+                        .collect()
+                })
+                .collect()
+        })
+        .collect()
+}
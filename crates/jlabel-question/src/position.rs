@@ -1,6 +1,13 @@
 //! Structures for position
 
-use std::{fmt::Debug, ops::Range};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    fmt::{Debug, Display},
+    ops::Range,
+};
 
 use crate::Label;
 
@@ -26,6 +33,20 @@ pub enum AllPosition {
     Undefined(UndefinedPotision),
 }
 
+impl Display for AllPosition {
+    /// Formats the position as its field code (e.g. `F5`).
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Phone(position) => write!(f, "{position:?}"),
+            Self::SignedRange(position) => write!(f, "{position:?}"),
+            Self::UnsignedRange(position) => write!(f, "{position:?}"),
+            Self::Boolean(position) => write!(f, "{position:?}"),
+            Self::Category(position) => write!(f, "{position:?}"),
+            Self::Undefined(position) => write!(f, "{position:?}"),
+        }
+    }
+}
+
 macro_rules! as_ref_map {
     ($label:ident.$block:ident.$prop:ident) => {
         $label.$block.as_ref().map(|b| &b.$prop)
@@ -404,7 +425,7 @@ mod tests {
 
     #[test]
     fn range_fail() {
-        use std::num::IntErrorKind;
+        use core::num::IntErrorKind;
         assert!(matches!(
             range_u8("?2"),
             Err(ParseError::FailLiteral(e)) if *e.kind() == IntErrorKind::InvalidDigit
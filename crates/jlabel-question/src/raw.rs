@@ -0,0 +1,265 @@
+//! Extraction of fields from full-context label string
+
+use crate::position::{
+    AllPosition, BooleanPosition::*, CategoryPosition::*, PhonePosition::*, SignedRangePosition::*,
+    UndefinedPotision::*, UnsignedRangePosition::*,
+};
+use AllPosition::*;
+
+use alloc::string::String;
+use core::ops::Range;
+
+/// The start marker, the delimiters, and the end marker of each block.
+const BLOCKS: [(&str, &[&str], &str); 12] = [
+    ("", &["^", "-", "+", "="], "/A:"),
+    ("/A:", &["+", "+"], "/B:"),
+    ("/B:", &["-", "_"], "/C:"),
+    ("/C:", &["_", "+"], "/D:"),
+    ("/D:", &["+", "_"], "/E:"),
+    ("/E:", &["_", "!", "_", "-"], "/F:"),
+    ("/F:", &["_", "#", "_", "@", "_", "|", "_"], "/G:"),
+    ("/G:", &["_", "%", "_", "_"], "/H:"),
+    ("/H:", &["_"], "/I:"),
+    ("/I:", &["-", "@", "+", "&", "-", "|", "+"], "/J:"),
+    ("/J:", &["_"], "/K:"),
+    ("/K:", &["+", "-"], ""),
+];
+
+/// All the positions, in the order they appear in full-context label.
+pub(crate) const POSITIONS: [AllPosition; 50] = [
+    Phone(P1),
+    Phone(P2),
+    Phone(P3),
+    Phone(P4),
+    Phone(P5),
+    SignedRange(A1),
+    UnsignedRange(A2),
+    UnsignedRange(A3),
+    Category(B1),
+    Category(B2),
+    Category(B3),
+    Category(C1),
+    Category(C2),
+    Category(C3),
+    Category(D1),
+    Category(D2),
+    Category(D3),
+    UnsignedRange(E1),
+    UnsignedRange(E2),
+    Boolean(E3),
+    Undefined(E4),
+    Boolean(E5),
+    UnsignedRange(F1),
+    UnsignedRange(F2),
+    Boolean(F3),
+    Undefined(F4),
+    UnsignedRange(F5),
+    UnsignedRange(F6),
+    UnsignedRange(F7),
+    UnsignedRange(F8),
+    UnsignedRange(G1),
+    UnsignedRange(G2),
+    Boolean(G3),
+    Undefined(G4),
+    Boolean(G5),
+    UnsignedRange(H1),
+    UnsignedRange(H2),
+    UnsignedRange(I1),
+    UnsignedRange(I2),
+    UnsignedRange(I3),
+    UnsignedRange(I4),
+    UnsignedRange(I5),
+    UnsignedRange(I6),
+    UnsignedRange(I7),
+    UnsignedRange(I8),
+    UnsignedRange(J1),
+    UnsignedRange(J2),
+    UnsignedRange(K1),
+    UnsignedRange(K2),
+    UnsignedRange(K3),
+];
+
+/// Returns the string at the position of full-context label string,
+/// or `None` if the label is malformed.
+///
+/// Only the block containing the position is scanned.
+pub(crate) fn field(label: &str, position: AllPosition) -> Option<&str> {
+    span(label, position).map(|span| &label[span])
+}
+
+/// Returns the byte range of the field at the position of full-context label string,
+/// or `None` if the label is malformed.
+pub(crate) fn span(label: &str, position: AllPosition) -> Option<Range<usize>> {
+    let (block, index) = locate(position);
+    let (start, delimiters, end) = BLOCKS[block];
+
+    let mut offset = match start {
+        "" => 0,
+        start => label.find(start)? + start.len(),
+    };
+    let block_end = match end {
+        "" => label.len(),
+        end => offset + label[offset..].find(end)?,
+    };
+
+    for delimiter in &delimiters[..index] {
+        offset += label[offset..block_end].find(delimiter)? + delimiter.len();
+    }
+    let field_end = match delimiters.get(index) {
+        Some(delimiter) => offset + label[offset..block_end].find(delimiter)?,
+        None => block_end,
+    };
+    Some(offset..field_end)
+}
+
+/// Builds full-context label string from the strings of all the fields.
+#[cfg_attr(not(feature = "regex"), allow(dead_code))]
+pub(crate) fn build(mut field: impl FnMut(AllPosition) -> String) -> String {
+    let mut label = String::new();
+    let mut positions = POSITIONS.into_iter();
+    for (start, delimiters, _) in BLOCKS {
+        label.push_str(start);
+        for delimiter in delimiters {
+            label.push_str(&field(positions.next().unwrap()));
+            label.push_str(delimiter);
+        }
+        label.push_str(&field(positions.next().unwrap()));
+    }
+    label
+}
+
+/// Returns the strings right before and after the field at the position.
+///
+/// They are empty for the first and the last field respectively.
+#[cfg_attr(
+    not(any(feature = "arbitrary", feature = "proptest")),
+    allow(dead_code)
+)]
+pub(crate) fn context(position: AllPosition) -> (&'static str, &'static str) {
+    let (block, index) = locate(position);
+    let (start, delimiters, end) = BLOCKS[block];
+    let before = match index {
+        0 => start,
+        index => delimiters[index - 1],
+    };
+    let after = delimiters.get(index).copied().unwrap_or(end);
+    (before, after)
+}
+
+/// Returns the index of block and the index of field in the block.
+fn locate(position: AllPosition) -> (usize, usize) {
+    match position {
+        Phone(P1) => (0, 0),
+        Phone(P2) => (0, 1),
+        Phone(P3) => (0, 2),
+        Phone(P4) => (0, 3),
+        Phone(P5) => (0, 4),
+
+        SignedRange(A1) => (1, 0),
+        UnsignedRange(A2) => (1, 1),
+        UnsignedRange(A3) => (1, 2),
+
+        Category(B1) => (2, 0),
+        Category(B2) => (2, 1),
+        Category(B3) => (2, 2),
+
+        Category(C1) => (3, 0),
+        Category(C2) => (3, 1),
+        Category(C3) => (3, 2),
+
+        Category(D1) => (4, 0),
+        Category(D2) => (4, 1),
+        Category(D3) => (4, 2),
+
+        UnsignedRange(E1) => (5, 0),
+        UnsignedRange(E2) => (5, 1),
+        Boolean(E3) => (5, 2),
+        Undefined(E4) => (5, 3),
+        Boolean(E5) => (5, 4),
+
+        UnsignedRange(F1) => (6, 0),
+        UnsignedRange(F2) => (6, 1),
+        Boolean(F3) => (6, 2),
+        Undefined(F4) => (6, 3),
+        UnsignedRange(F5) => (6, 4),
+        UnsignedRange(F6) => (6, 5),
+        UnsignedRange(F7) => (6, 6),
+        UnsignedRange(F8) => (6, 7),
+
+        UnsignedRange(G1) => (7, 0),
+        UnsignedRange(G2) => (7, 1),
+        Boolean(G3) => (7, 2),
+        Undefined(G4) => (7, 3),
+        Boolean(G5) => (7, 4),
+
+        UnsignedRange(H1) => (8, 0),
+        UnsignedRange(H2) => (8, 1),
+
+        UnsignedRange(I1) => (9, 0),
+        UnsignedRange(I2) => (9, 1),
+        UnsignedRange(I3) => (9, 2),
+        UnsignedRange(I4) => (9, 3),
+        UnsignedRange(I5) => (9, 4),
+        UnsignedRange(I6) => (9, 5),
+        UnsignedRange(I7) => (9, 6),
+        UnsignedRange(I8) => (9, 7),
+
+        UnsignedRange(J1) => (10, 0),
+        UnsignedRange(J2) => (10, 1),
+
+        UnsignedRange(K1) => (11, 0),
+        UnsignedRange(K2) => (11, 1),
+        UnsignedRange(K3) => (11, 2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_LABEL: &str = "n^i-o+i=cl/A:2+3+1/B:04-xx_xx/C:13_xx+xx/D:20+1_1/E:xx_xx!xx_xx-xx/F:3_1#0_xx@1_4|1_12/G:3_3%0_xx_1/H:xx_xx/I:4-12@1+2&1-6|1+21/J:2_9/K:2+6-21";
+
+    #[test]
+    fn fields() {
+        assert_eq!(field(TEST_LABEL, Phone(P1)), Some("n"));
+        assert_eq!(field(TEST_LABEL, Phone(P5)), Some("cl"));
+        assert_eq!(field(TEST_LABEL, SignedRange(A1)), Some("2"));
+        assert_eq!(field(TEST_LABEL, UnsignedRange(A3)), Some("1"));
+        assert_eq!(field(TEST_LABEL, Category(B1)), Some("04"));
+        assert_eq!(field(TEST_LABEL, Category(D3)), Some("1"));
+        assert_eq!(field(TEST_LABEL, Boolean(E5)), Some("xx"));
+        assert_eq!(field(TEST_LABEL, UnsignedRange(F8)), Some("12"));
+        assert_eq!(field(TEST_LABEL, Boolean(G5)), Some("1"));
+        assert_eq!(field(TEST_LABEL, UnsignedRange(I4)), Some("2"));
+        assert_eq!(field(TEST_LABEL, UnsignedRange(I8)), Some("21"));
+        assert_eq!(field(TEST_LABEL, UnsignedRange(K3)), Some("21"));
+    }
+
+    #[test]
+    fn build_label() {
+        let label = build(|position| {
+            field(TEST_LABEL, position)
+                .expect("TEST_LABEL is well-formed")
+                .to_string()
+        });
+        assert_eq!(label, TEST_LABEL);
+        assert_eq!(span(TEST_LABEL, Phone(P5)), Some(8..10));
+    }
+
+    #[test]
+    fn positions_in_order() {
+        let mut located = Vec::new();
+        for (block, (_, delimiters, _)) in BLOCKS.iter().enumerate() {
+            located.extend((0..=delimiters.len()).map(|index| (block, index)));
+        }
+        assert_eq!(POSITIONS.map(locate).as_slice(), located);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(field("", Phone(P1)), None);
+        assert_eq!(field("a^b-c+d=e/A:1+2", UnsignedRange(A2)), None);
+        assert_eq!(field("a^b-c+d=e/A:1+2+3/B:", UnsignedRange(A3)), Some("3"));
+        assert_eq!(field("a^b-c+d=e/A:1+2+3/B:", UnsignedRange(K1)), None);
+    }
+}
@@ -1,20 +1,59 @@
 //! Fallback using regular expression
 
+use std::fmt::{Display, Formatter};
+
 use jlabel::Label;
 use regex_automata::{Anchored, Input, meta::Regex};
-use regex_syntax::hir::{Dot, Hir, Repetition};
+use regex_syntax::hir::{Dot, Hir, Look, Repetition};
 
 use crate::{ParseError, QuestionMatcher};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 /// A fallback structure for parsing and checking of question.
 ///
 /// Requires `regex` trait.
 ///
 /// Please note that this is only for fallback of [`crate::AllQuestion`], and is not intended to be used independently.
+///
+/// With `serde` feature, this is serialized as the source patterns,
+/// and the regex is compiled again on deserialization.
 #[derive(Debug, Clone)]
-pub struct RegexQuestion(Regex);
+pub struct RegexQuestion {
+    regex: Regex,
+    patterns: Vec<String>,
+}
+
+impl PartialEq for RegexQuestion {
+    fn eq(&self, other: &Self) -> bool {
+        self.patterns == other.patterns
+    }
+}
+
+impl Eq for RegexQuestion {}
+
+#[cfg(feature = "serde")]
+impl Serialize for RegexQuestion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.patterns.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for RegexQuestion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let patterns = Vec::<String>::deserialize(deserializer)?;
+        Self::parse(&patterns).map_err(serde::de::Error::custom)
+    }
+}
 
 impl RegexQuestion {
+    /// The source patterns of this question.
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
     fn parse_wildcard<S: AsRef<str>>(pattern: S) -> Hir {
         Hir::concat(
             pattern
@@ -33,21 +72,75 @@ impl RegexQuestion {
                 .collect(),
         )
     }
+
+    /// Parses the patterns so that they must match the entire label, not only its prefix.
+    pub(crate) fn parse_anchored<S: AsRef<str>>(patterns: &[S]) -> Result<Self, ParseError> {
+        Self::build(patterns, |pattern| {
+            Hir::concat(vec![Self::parse_wildcard(pattern), Hir::look(Look::End)])
+        })
+    }
+
+    fn build<S: AsRef<str>>(patterns: &[S], hir: impl Fn(&str) -> Hir) -> Result<Self, ParseError> {
+        let regex = Regex::builder()
+            .build_many_from_hir(
+                &patterns
+                    .iter()
+                    .map(|pattern| hir(pattern.as_ref()))
+                    .collect::<Vec<_>>(),
+            )
+            .or(Err(ParseError::FailRegex))?;
+        Ok(Self {
+            regex,
+            patterns: patterns.iter().map(|p| p.as_ref().to_string()).collect(),
+        })
+    }
+
+    /// Checks if the full-context label matches the question,
+    /// and reports which pattern alternative matched.
+    pub fn explain(&self, label: &Label) -> RegexExplanation {
+        let label = label.to_string();
+        let matched = self
+            .regex
+            .search(&Input::new(&label).anchored(Anchored::Yes))
+            .map(|m| {
+                let index = m.pattern().as_usize();
+                (index, self.patterns[index].clone())
+            });
+        RegexExplanation { matched }
+    }
+}
+
+/// The report of why a [`RegexQuestion`] did or did not match a label.
+///
+/// This is the result of [`RegexQuestion::explain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegexExplanation {
+    /// The index and the source of the pattern alternative that matched,
+    /// or `None` if no pattern matched.
+    pub matched: Option<(usize, String)>,
+}
+
+impl Display for RegexExplanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.matched {
+            Some((index, pattern)) => write!(f, "matched pattern #{index} `{pattern}`"),
+            None => f.write_str("no pattern matched"),
+        }
+    }
 }
 
 impl QuestionMatcher for RegexQuestion {
     fn parse<S: AsRef<str>>(patterns: &[S]) -> Result<Self, ParseError> {
-        let regex = Regex::builder()
-            .build_from_hir(&Hir::alternation(
-                patterns.iter().map(Self::parse_wildcard).collect(),
-            ))
-            .or(Err(ParseError::FailRegex))?;
-        Ok(Self(regex))
+        Self::build(patterns, |pattern| Self::parse_wildcard(pattern))
     }
     fn test(&self, label: &Label) -> bool {
-        self.0
+        self.regex
             .is_match(Input::new(&label.to_string()).anchored(Anchored::Yes))
     }
+    fn test_str(&self, label: &str) -> bool {
+        self.regex
+            .is_match(Input::new(label).anchored(Anchored::Yes))
+    }
 }
 
 #[cfg(test)]
@@ -68,6 +161,55 @@ mod tests {
         assert!(!RegexQuestion::parse(&["INVALID?*"]).unwrap().test(&label));
 
         assert!(!RegexQuestion::parse(&["^k-o+*"]).unwrap().test(&label));
+
+        assert!(
+            RegexQuestion::parse(&["*^k-o+*"])
+                .unwrap()
+                .test_str(TEST_LABEL)
+        );
+        assert!(
+            !RegexQuestion::parse(&["^k-o+*"])
+                .unwrap()
+                .test_str(TEST_LABEL)
+        );
+    }
+    #[test]
+    fn explain() {
+        const TEST_LABEL: &str = "sil^k-o+N=n/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5";
+
+        use crate::QuestionMatcher;
+        use jlabel::Label;
+        use std::str::FromStr;
+
+        let label = Label::from_str(TEST_LABEL).unwrap();
+
+        let question = RegexQuestion::parse(&["*^a-*", "*^k-o+*"]).unwrap();
+        let explanation = question.explain(&label);
+        assert_eq!(explanation.matched, Some((1, "*^k-o+*".to_string())));
+        assert_eq!(explanation.to_string(), "matched pattern #1 `*^k-o+*`");
+
+        let question = RegexQuestion::parse(&["*^a-*"]).unwrap();
+        let explanation = question.explain(&label);
+        assert_eq!(explanation.matched, None);
+        assert_eq!(explanation.to_string(), "no pattern matched");
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        use crate::QuestionMatcher;
+        use serde::{
+            Deserialize,
+            de::{
+                IntoDeserializer,
+                value::{Error, SeqDeserializer},
+            },
+        };
+
+        let patterns = vec!["*^a-*".to_string(), "*^k-o+*".to_string()];
+        let deserializer: SeqDeserializer<_, Error> = patterns.clone().into_deserializer();
+        let question = RegexQuestion::deserialize(deserializer).unwrap();
+        assert_eq!(question, RegexQuestion::parse(&patterns).unwrap());
+        assert_eq!(question.patterns(), patterns);
     }
     #[test]
     fn wildcard() {
@@ -0,0 +1,611 @@
+//! Statistics of questions over a label corpus
+
+use std::io::{self, Write};
+
+use jlabel::Label;
+
+use crate::QuestionMatcher;
+
+#[cfg(feature = "regex")]
+use crate::{AnyQuestion, ParseError};
+
+/// Hit counts and redundancy of a question set over a set of labels.
+///
+/// ```rust
+/// # use std::error::Error;
+/// use jlabel::Label;
+/// use jlabel_question::{AllQuestion, QuestionMatcher, stats::QuestionStats};
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let questions = [
+///     ("C-Phone_k", AllQuestion::parse(&["*-k+*"])?),
+///     ("C-Phone_o", AllQuestion::parse(&["*-o+*"])?),
+///     ("C-Phone_z", AllQuestion::parse(&["*-z+*"])?),
+/// ];
+/// let labels: Vec<Label> = [
+///     "xx^sil-k+o=N/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5",
+///     "sil^k-o+N=n/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5",
+/// ]
+/// .iter()
+/// .map(|s| s.parse())
+/// .collect::<Result<_, _>>()?;
+///
+/// let stats = QuestionStats::collect(&questions, &labels);
+/// assert_eq!(stats.hits().collect::<Vec<_>>(), [("C-Phone_k", 1), ("C-Phone_o", 1), ("C-Phone_z", 0)]);
+/// assert_eq!(stats.never_fired(), ["C-Phone_z"]);
+/// #
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuestionStats {
+    names: Vec<String>,
+    tally: Tally,
+}
+
+impl QuestionStats {
+    /// Tests every question against every label, and collects the statistics.
+    pub fn collect<'a, N, Q, I>(questions: &[(N, Q)], labels: I) -> Self
+    where
+        N: AsRef<str>,
+        Q: QuestionMatcher,
+        I: IntoIterator<Item = &'a Label>,
+    {
+        Self::new(questions, Tally::collect(questions, labels))
+    }
+
+    /// Same as [`QuestionStats::collect`], but tests the label sequences in parallel.
+    ///
+    /// The result is identical to [`QuestionStats::collect`] over the concatenated sequences.
+    /// Each sequence is tallied on its own, and the tallies are merged,
+    /// so the answers are not kept for every label.
+    ///
+    /// Requires `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn collect_par<N, Q, S>(questions: &[(N, Q)], sequences: &[S]) -> Self
+    where
+        N: AsRef<str> + Sync,
+        Q: QuestionMatcher + Sync,
+        S: AsRef<[Label]> + Sync,
+    {
+        use rayon::prelude::*;
+
+        let tally = sequences
+            .par_iter()
+            .map(|sequence| Tally::collect(questions, sequence.as_ref()))
+            .reduce(|| Tally::new(questions.len()), Tally::merge);
+        Self::new(questions, tally)
+    }
+
+    fn new<N: AsRef<str>, Q>(questions: &[(N, Q)], tally: Tally) -> Self {
+        Self {
+            names: questions
+                .iter()
+                .map(|(name, _)| name.as_ref().to_string())
+                .collect(),
+            tally,
+        }
+    }
+
+    /// The number of labels tested.
+    pub fn label_count(&self) -> usize {
+        self.tally.label_count
+    }
+
+    /// The name and the number of labels matched, for each question.
+    pub fn hits(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.names
+            .iter()
+            .map(String::as_str)
+            .zip(self.tally.hits.iter().copied())
+    }
+
+    /// The names of questions that matched none of the labels.
+    pub fn never_fired(&self) -> Vec<&str> {
+        self.hits()
+            .filter(|(_, hits)| *hits == 0)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// The names of questions that matched all of the labels.
+    ///
+    /// There are none if no labels have been tested.
+    pub fn always_fired(&self) -> Vec<&str> {
+        if self.tally.label_count == 0 {
+            return Vec::new();
+        }
+        self.hits()
+            .filter(|(_, hits)| *hits == self.tally.label_count)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Groups of two or more questions that answered identically to every label.
+    ///
+    /// Only one question of each group is needed to split the labels.
+    /// There are no groups if no labels have been tested.
+    pub fn redundant_groups(&self) -> Vec<Vec<&str>> {
+        if self.tally.label_count == 0 {
+            return Vec::new();
+        }
+        let mut groups: Vec<Vec<&str>> = Vec::new();
+        let mut group_of_class = vec![usize::MAX; self.tally.classes.len()];
+        for (name, class) in self.names.iter().zip(&self.tally.classes) {
+            match group_of_class[*class] {
+                usize::MAX => {
+                    group_of_class[*class] = groups.len();
+                    groups.push(vec![name]);
+                }
+                group => groups[group].push(name),
+            }
+        }
+        groups.retain(|group| group.len() > 1);
+        groups
+    }
+
+    /// Writes the statistics as CSV.
+    ///
+    /// The columns are the name of question, the number of hits, the ratio of hits,
+    /// and the index of redundant group (empty if the question is not redundant).
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let groups = self.group_indices();
+        writeln!(writer, "question,hits,ratio,redundant_group")?;
+        for ((name, hits), group) in self.hits().zip(groups) {
+            write_csv_field(&mut writer, name)?;
+            write!(writer, ",{hits},{},", self.ratio(hits))?;
+            if let Some(group) = group {
+                write!(writer, "{group}")?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the statistics as JSON.
+    ///
+    /// ```json
+    /// {
+    ///   "labels": 2,
+    ///   "questions": [{"name": "C-Phone_k", "hits": 1, "ratio": 0.5}],
+    ///   "never_fired": [],
+    ///   "always_fired": [],
+    ///   "redundant_groups": []
+    /// }
+    /// ```
+    pub fn write_json<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(
+            writer,
+            "{{\"labels\":{},\"questions\":[",
+            self.tally.label_count
+        )?;
+        for (i, (name, hits)) in self.hits().enumerate() {
+            if i > 0 {
+                writer.write_all(b",")?;
+            }
+            writer.write_all(b"{\"name\":")?;
+            write_json_string(&mut writer, name)?;
+            write!(writer, ",\"hits\":{hits},\"ratio\":{}}}", self.ratio(hits))?;
+        }
+        writer.write_all(b"],\"never_fired\":")?;
+        write_json_array(&mut writer, &self.never_fired())?;
+        writer.write_all(b",\"always_fired\":")?;
+        write_json_array(&mut writer, &self.always_fired())?;
+        writer.write_all(b",\"redundant_groups\":[")?;
+        for (i, group) in self.redundant_groups().iter().enumerate() {
+            if i > 0 {
+                writer.write_all(b",")?;
+            }
+            write_json_array(&mut writer, group)?;
+        }
+        writer.write_all(b"]}")?;
+        Ok(())
+    }
+
+    fn ratio(&self, hits: usize) -> f64 {
+        if self.tally.label_count == 0 {
+            0.0
+        } else {
+            hits as f64 / self.tally.label_count as f64
+        }
+    }
+
+    fn group_indices(&self) -> Vec<Option<usize>> {
+        if self.tally.label_count == 0 {
+            return vec![None; self.tally.classes.len()];
+        }
+        let mut members = vec![0usize; self.tally.classes.len()];
+        for class in &self.tally.classes {
+            members[*class] += 1;
+        }
+        let mut group_of_class = vec![None; self.tally.classes.len()];
+        let mut group_count = 0;
+        self.tally
+            .classes
+            .iter()
+            .map(|class| {
+                if members[*class] < 2 {
+                    return None;
+                }
+                Some(*group_of_class[*class].get_or_insert_with(|| {
+                    group_count += 1;
+                    group_count - 1
+                }))
+            })
+            .collect()
+    }
+}
+
+/// Hit counts of questions, and which questions answered identically, over some labels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Tally {
+    hits: Vec<usize>,
+    label_count: usize,
+    /// Questions with the same class have answered identically to all the labels so far.
+    /// The classes are numbered from zero in the order of their first questions.
+    classes: Vec<usize>,
+}
+
+impl Tally {
+    fn new(question_count: usize) -> Self {
+        Self {
+            hits: vec![0; question_count],
+            label_count: 0,
+            classes: vec![0; question_count],
+        }
+    }
+
+    fn collect<'a, N, Q, I>(questions: &[(N, Q)], labels: I) -> Self
+    where
+        Q: QuestionMatcher,
+        I: IntoIterator<Item = &'a Label>,
+    {
+        let mut tally = Self::new(questions.len());
+        let mut answers = Vec::with_capacity(questions.len());
+        let mut remap = Vec::new();
+        for label in labels {
+            answers.clear();
+            answers.extend(questions.iter().map(|(_, question)| question.test(label)));
+            tally.push(&answers, &mut remap);
+        }
+        tally
+    }
+
+    fn push(&mut self, answers: &[bool], remap: &mut Vec<usize>) {
+        self.label_count += 1;
+
+        // Split every class by the answer, and renumber the classes from zero.
+        remap.clear();
+        remap.resize(self.classes.len() * 2, usize::MAX);
+        let mut class_count = 0;
+        for ((class, hits), answer) in self.classes.iter_mut().zip(&mut self.hits).zip(answers) {
+            if *answer {
+                *hits += 1;
+            }
+            let key = *class * 2 + usize::from(*answer);
+            if remap[key] == usize::MAX {
+                remap[key] = class_count;
+                class_count += 1;
+            }
+            *class = remap[key];
+        }
+    }
+
+    /// Merges the tallies over two disjoint sets of labels.
+    #[cfg(feature = "rayon")]
+    fn merge(mut self, other: Self) -> Self {
+        self.label_count += other.label_count;
+        for (hits, other) in self.hits.iter_mut().zip(other.hits) {
+            *hits += other;
+        }
+
+        // Questions answered identically to all the labels only if they did in both.
+        let mut remap = std::collections::BTreeMap::new();
+        for (class, other) in self.classes.iter_mut().zip(other.classes) {
+            let class_count = remap.len();
+            *class = *remap.entry((*class, other)).or_insert(class_count);
+        }
+        self
+    }
+}
+
+/// How many questions in a set are running on the regex fallback, and why.
+///
+/// Requires `regex` feature.
+///
+/// ```rust
+/// use jlabel_question::{AnyQuestion, QuestionMatcher, stats::FallbackStats};
+///
+/// let questions = [
+///     ("C-Phone_k", AnyQuestion::parse(&["*-k+*"]).unwrap()),
+///     ("LC-Phone_k_o", AnyQuestion::parse(&["*^k-o+*"]).unwrap()),
+/// ];
+///
+/// let stats = FallbackStats::collect(&questions);
+/// assert_eq!(stats.structured_count(), 1);
+/// assert_eq!(stats.to_string(), "1 of 2 questions on regex fallback\nLC-Phone_k_o: Invalid position");
+/// ```
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FallbackStats {
+    question_count: usize,
+    fallbacks: Vec<(String, Option<ParseError>)>,
+}
+
+#[cfg(feature = "regex")]
+impl FallbackStats {
+    /// Collects the questions which failed to parse as [`crate::AllQuestion`].
+    pub fn collect<N: AsRef<str>>(questions: &[(N, AnyQuestion)]) -> Self {
+        Self {
+            question_count: questions.len(),
+            fallbacks: questions
+                .iter()
+                .filter(|(_, question)| !question.is_structured())
+                .map(|(name, question)| {
+                    (
+                        name.as_ref().to_string(),
+                        question.fallback_reason().cloned(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// The number of questions.
+    pub fn question_count(&self) -> usize {
+        self.question_count
+    }
+
+    /// The number of questions parsed as [`crate::AllQuestion`].
+    pub fn structured_count(&self) -> usize {
+        self.question_count - self.fallbacks.len()
+    }
+
+    /// The number of questions on regex fallback.
+    pub fn fallback_count(&self) -> usize {
+        self.fallbacks.len()
+    }
+
+    /// The names of questions on regex fallback, with the error which forced the fallback.
+    ///
+    /// The error is `None` if the question was not created by [`AnyQuestion::parse`],
+    /// e.g. constructed as [`AnyQuestion::Regex`] directly or deserialized.
+    pub fn fallbacks(&self) -> impl Iterator<Item = (&str, Option<&ParseError>)> {
+        self.fallbacks
+            .iter()
+            .map(|(name, reason)| (name.as_str(), reason.as_ref()))
+    }
+}
+
+#[cfg(feature = "regex")]
+impl std::fmt::Display for FallbackStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} of {} questions on regex fallback",
+            self.fallback_count(),
+            self.question_count
+        )?;
+        for (name, reason) in &self.fallbacks {
+            match reason {
+                Some(reason) => write!(f, "\n{name}: {reason}")?,
+                None => write!(f, "\n{name}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_csv_field<W: Write>(writer: &mut W, field: &str) -> io::Result<()> {
+    if field.contains([',', '"', '\n', '\r']) {
+        write!(writer, "\"{}\"", field.replace('"', "\"\""))
+    } else {
+        writer.write_all(field.as_bytes())
+    }
+}
+
+fn write_json_string<W: Write>(writer: &mut W, s: &str) -> io::Result<()> {
+    writer.write_all(b"\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => writer.write_all(b"\\\"")?,
+            '\\' => writer.write_all(b"\\\\")?,
+            '\n' => writer.write_all(b"\\n")?,
+            '\r' => writer.write_all(b"\\r")?,
+            '\t' => writer.write_all(b"\\t")?,
+            c if c.is_control() => write!(writer, "\\u{:04x}", c as u32)?,
+            c => write!(writer, "{c}")?,
+        }
+    }
+    writer.write_all(b"\"")
+}
+
+fn write_json_array<W: Write>(writer: &mut W, values: &[&str]) -> io::Result<()> {
+    writer.write_all(b"[")?;
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        write_json_string(writer, value)?;
+    }
+    writer.write_all(b"]")
+}
+
+#[cfg(test)]
+mod tests {
+    use jlabel::Label;
+
+    use crate::{AllQuestion, QuestionMatcher};
+
+    use super::QuestionStats;
+
+    const LABELS: [&str; 4] = [
+        "xx^xx-sil+k=o/A:xx+xx+xx/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:xx_xx#xx_xx@xx_xx|xx_xx/G:5_5%0_xx_xx/H:xx_xx/I:xx-xx@xx+xx&xx-xx|xx+xx/J:1_5/K:1+1-5",
+        "xx^sil-k+o=N/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5",
+        "sil^k-o+N=n/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5",
+        "k^o-N+n=i/A:-3+2+4/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5",
+    ];
+
+    fn stats() -> QuestionStats {
+        let labels: Vec<Label> = LABELS.iter().map(|s| s.parse().unwrap()).collect();
+        let questions = [
+            ("K1=1", AllQuestion::parse(&["*/K:1+*"]).unwrap()),
+            ("A2=1", AllQuestion::parse(&["*+1+*"]).unwrap()),
+            (
+                "A1<=-4",
+                AllQuestion::parse(&["*/A:-4+*", "*/A:-?+*"]).unwrap(),
+            ),
+            ("C1=09", AllQuestion::parse(&["*/C:09_*"]).unwrap()),
+            ("P3=sil", AllQuestion::parse(&["*-sil+*"]).unwrap()),
+            ("P3=z", AllQuestion::parse(&["*-z+*"]).unwrap()),
+            ("K3=5", AllQuestion::parse(&["*-5"]).unwrap()),
+        ];
+        QuestionStats::collect(&questions, &labels)
+    }
+
+    #[test]
+    fn counts() {
+        let stats = stats();
+        assert_eq!(stats.label_count(), 4);
+        assert_eq!(
+            stats.hits().collect::<Vec<_>>(),
+            [
+                ("K1=1", 4),
+                ("A2=1", 2),
+                ("A1<=-4", 3),
+                ("C1=09", 3),
+                ("P3=sil", 1),
+                ("P3=z", 0),
+                ("K3=5", 4),
+            ]
+        );
+        assert_eq!(stats.never_fired(), ["P3=z"]);
+        assert_eq!(stats.always_fired(), ["K1=1", "K3=5"]);
+        assert_eq!(
+            stats.redundant_groups(),
+            [vec!["K1=1", "K3=5"], vec!["A1<=-4", "C1=09"]]
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn collect_par() {
+        let labels: Vec<Label> = LABELS.iter().map(|s| s.parse().unwrap()).collect();
+        let questions = [
+            ("A2=1", AllQuestion::parse(&["*+1+*"]).unwrap()),
+            ("P3=sil", AllQuestion::parse(&["*-sil+*"]).unwrap()),
+            ("P3=z", AllQuestion::parse(&["*-z+*"]).unwrap()),
+        ];
+        let sequences = [&labels[..1], &[], &labels[1..3], &labels[3..]];
+        assert_eq!(
+            QuestionStats::collect_par(&questions, &sequences),
+            QuestionStats::collect(&questions, &labels)
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let questions = [
+            ("P3=z", AllQuestion::parse(&["*-z+*"]).unwrap()),
+            ("P3=k", AllQuestion::parse(&["*-k+*"]).unwrap()),
+            ("K3=5", AllQuestion::parse(&["*-5"]).unwrap()),
+        ];
+        let stats = QuestionStats::collect(&questions, &[]);
+        assert_eq!(stats.label_count(), 0);
+        assert_eq!(stats.never_fired(), ["P3=z", "P3=k", "K3=5"]);
+        assert_eq!(stats.always_fired(), Vec::<&str>::new());
+        assert_eq!(stats.redundant_groups(), Vec::<Vec<&str>>::new());
+
+        let mut csv = Vec::new();
+        stats.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "question,hits,ratio,redundant_group\nP3=z,0,0,\nP3=k,0,0,\nK3=5,0,0,\n"
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn fallback() {
+        use super::FallbackStats;
+        use crate::{AnyQuestion, ParseError, regex::RegexQuestion};
+
+        let questions = [
+            ("K1=1", AnyQuestion::parse(&["*/K:1+*"]).unwrap()),
+            ("P2=k|K3=5", AnyQuestion::parse(&["*^k-*", "*-5"]).unwrap()),
+            ("Empty", AnyQuestion::parse::<&str>(&[]).unwrap()),
+            (
+                "Manual",
+                AnyQuestion::Regex {
+                    question: RegexQuestion::parse(&["*-5"]).unwrap(),
+                    reason: None,
+                },
+            ),
+        ];
+        assert!(questions[0].1.is_structured());
+        assert_eq!(questions[0].1.fallback_reason(), None);
+        assert!(!questions[1].1.is_structured());
+        assert_eq!(
+            questions[1].1.fallback_reason(),
+            Some(&ParseError::PositionMismatch)
+        );
+
+        let stats = FallbackStats::collect(&questions);
+        assert_eq!(stats.question_count(), 4);
+        assert_eq!(stats.structured_count(), 1);
+        assert_eq!(stats.fallback_count(), 3);
+        assert_eq!(
+            stats.fallbacks().collect::<Vec<_>>(),
+            [
+                ("P2=k|K3=5", Some(&ParseError::PositionMismatch)),
+                ("Empty", Some(&ParseError::Empty)),
+                ("Manual", None),
+            ]
+        );
+        assert_eq!(
+            stats.to_string(),
+            "3 of 4 questions on regex fallback\nP2=k|K3=5: Position mismatch\nEmpty: Empty patterns or range\nManual"
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let mut csv = Vec::new();
+        stats().write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            concat!(
+                "question,hits,ratio,redundant_group\n",
+                "K1=1,4,1,0\n",
+                "A2=1,2,0.5,\n",
+                "A1<=-4,3,0.75,1\n",
+                "C1=09,3,0.75,1\n",
+                "P3=sil,1,0.25,\n",
+                "P3=z,0,0,\n",
+                "K3=5,4,1,0\n",
+            )
+        );
+    }
+
+    #[test]
+    fn json() {
+        let questions = [
+            ("P3=\"k\"", AllQuestion::parse(&["*-k+*"]).unwrap()),
+            ("P3=z", AllQuestion::parse(&["*-z+*"]).unwrap()),
+        ];
+        let labels: Vec<Label> = LABELS.iter().map(|s| s.parse().unwrap()).collect();
+        let mut json = Vec::new();
+        QuestionStats::collect(&questions, &labels)
+            .write_json(&mut json)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            concat!(
+                r#"{"labels":4,"questions":["#,
+                r#"{"name":"P3=\"k\"","hits":1,"ratio":0.25},"#,
+                r#"{"name":"P3=z","hits":0,"ratio":0}],"#,
+                r#""never_fired":["P3=z"],"always_fired":[],"redundant_groups":[]}"#,
+            )
+        );
+    }
+}
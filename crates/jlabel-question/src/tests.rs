@@ -375,3 +375,95 @@ fn all_query() {
     });
     assert!(q.test(&nones));
 }
+
+#[test]
+fn explain() {
+    let label: Label = concat!(
+        "sil^n-i+h=o",
+        "/A:-3+1+7",
+        "/B:xx-xx_xx",
+        "/C:02_xx+xx",
+        "/D:02+xx_xx",
+        "/E:xx_xx!xx_xx-xx",
+        "/F:7_4#0_xx@1_3|1_12",
+        "/G:4_4%0_xx_1",
+        "/H:xx_xx",
+        "/I:3-12@1+2&1-8|1+41",
+        "/J:5_29",
+        "/K:2+8-41"
+    )
+    .parse()
+    .unwrap();
+
+    let explanation = AllQuestion::parse(&["*@2_*", "*@3_*"])
+        .unwrap()
+        .explain(&label);
+    assert_eq!(
+        explanation,
+        explain::Explanation {
+            position: AllPosition::UnsignedRange(UnsignedRangePosition::F5),
+            value: Some("1".to_string()),
+            expected: Some("2..4".to_string()),
+            is_match: false,
+        }
+    );
+    assert_eq!(
+        explanation.to_string(),
+        "F5 = 1, expected 2..4: not matched"
+    );
+
+    let explanation = AllQuestion::parse(&["*^n-*", "*^m-*"])
+        .unwrap()
+        .explain(&label);
+    assert_eq!(explanation.to_string(), "P2 = n, expected {n, m}: matched");
+
+    let explanation = AllQuestion::parse(&["*/E:xx_*"]).unwrap().explain(&label);
+    assert_eq!(explanation.to_string(), "E1 = xx, expected xx: matched");
+
+    let explanation = AllQuestion::parse(&["*_0/H:*"]).unwrap().explain(&label);
+    assert_eq!(explanation.to_string(), "G5 = 1, expected 0: not matched");
+
+    let explanation = AllQuestion::parse(&["*/C:01_*"]).unwrap().explain(&label);
+    assert_eq!(
+        explanation.to_string(),
+        "C1 = 02, expected {01}: not matched"
+    );
+
+    let explanation = AllQuestion::parse(&["*+1/D:*"]).unwrap().explain(&label);
+    assert_eq!(
+        explanation.to_string(),
+        "C3 = xx, expected {1}: not matched"
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn any_question() {
+    const TEST_LABEL: &str = "sil^k-o+N=n/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5";
+    let label: Label = TEST_LABEL.parse().unwrap();
+
+    let question = AnyQuestion::parse(&["*/A:-??+*", "*/A:-?+*"]).unwrap();
+    assert_eq!(
+        question,
+        AnyQuestion::Structured(AllQuestion::parse(&["*/A:-??+*", "*/A:-?+*"]).unwrap())
+    );
+    assert!(question.test(&label));
+    assert!(question.test_str(TEST_LABEL));
+
+    let question = AnyQuestion::parse(&["*^k-o+*"]).unwrap();
+    assert_eq!(
+        question,
+        AnyQuestion::Regex {
+            question: regex::RegexQuestion::parse(&["*^k-o+*"]).unwrap(),
+            reason: Some(ParseError::InvalidPosition(
+                PositionError::SuffixVerifyError
+            )),
+        }
+    );
+    assert!(question.test(&label));
+    assert!(question.test_str(TEST_LABEL));
+
+    let question = AnyQuestion::parse(&["*^a-o+*"]).unwrap();
+    assert!(!question.test(&label));
+    assert!(!question.test_str(TEST_LABEL));
+}
@@ -0,0 +1,247 @@
+//! Verification of [`AllQuestion`] against regex matching
+//!
+//! [`AllQuestion`] is expected to answer exactly the same as HTS-style string matching,
+//! which is what [`RegexQuestion`] does when the patterns must match the entire label.
+//! [`Verifier`] generates labels and reports the ones the two disagree on.
+//!
+//! Requires `regex` feature.
+//!
+//! ```rust
+//! use jlabel_question::verify::Verifier;
+//!
+//! let verifier = Verifier::new(&["*/A:-??+*", "*/A:-?+*"]).unwrap();
+//! assert!(verifier.exhaustive().is_empty());
+//! assert!(verifier.random(42, 1000).is_empty());
+//! ```
+
+use std::fmt::{Display, Formatter};
+
+use jlabel::Label;
+
+use crate::{
+    AllQuestion, ParseError, QuestionMatcher,
+    position::{AllPosition, CategoryPosition, UnsignedRangePosition},
+    raw,
+    regex::RegexQuestion,
+};
+
+/// Phonemes used in OpenJTalk.
+const PHONEMES: [&str; 43] = [
+    "a", "i", "u", "e", "o", "A", "I", "U", "E", "O", "N", "cl", "pau", "sil", "k", "ky", "g",
+    "gy", "s", "sh", "z", "j", "t", "ts", "ch", "ty", "d", "dy", "n", "ny", "h", "hy", "f", "b",
+    "by", "p", "py", "m", "my", "r", "ry", "w", "y",
+];
+
+/// The symbols separating phonemes in full-context label.
+const PHONEME_DELIMITERS: [char; 5] = ['^', '-', '+', '=', '/'];
+
+/// The labels [`Verifier::exhaustive`] starts from.
+const BASE_LABELS: [&str; 2] = [
+    "n^i-o+i=cl/A:2+3+1/B:04-xx_xx/C:13_xx+xx/D:20+1_1/E:xx_xx!xx_xx-xx/F:3_1#0_xx@1_4|1_12/G:3_3%0_xx_1/H:xx_xx/I:4-12@1+2&1-6|1+21/J:2_9/K:2+6-21",
+    "k^o-N+n=i/A:-3+2+4/B:02-3_1/C:09_xx+xx/D:xx+xx_xx/E:5_1!1_xx-0/F:5_5#1_xx@2_3|10_12/G:xx_xx%xx_xx_xx/H:4_12/I:1-5@2+1&1-1|1+5/J:xx_xx/K:2+3-17",
+];
+
+/// A label [`AllQuestion`] and [`RegexQuestion`] answered differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    /// The label.
+    pub label: Label,
+    /// The answer of [`AllQuestion`].
+    pub structured: bool,
+    /// The answer of [`RegexQuestion`].
+    pub regex: bool,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: AllQuestion {}, regex {}",
+            self.label, self.structured, self.regex
+        )
+    }
+}
+
+/// Checks if [`AllQuestion`] agrees with [`RegexQuestion`] on the patterns.
+#[derive(Debug, Clone)]
+pub struct Verifier {
+    structured: AllQuestion,
+    regex: RegexQuestion,
+}
+
+impl Verifier {
+    /// Parses the patterns as both [`AllQuestion`] and [`RegexQuestion`].
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Result<Self, ParseError> {
+        Ok(Self {
+            structured: AllQuestion::parse(patterns)?,
+            regex: RegexQuestion::parse_anchored(patterns)?,
+        })
+    }
+
+    /// Replaces the field the question is about with every value in its domain including `xx`,
+    /// and reports the labels the two disagree on.
+    ///
+    /// Numerical fields are generated over the whole range of its type,
+    /// i.e. from 0 (`A1` from the minimum) to the maximum.
+    pub fn exhaustive(&self) -> Vec<Disagreement> {
+        let position = self.structured.position();
+        let domain = self.domain(position);
+
+        let mut disagreements = Vec::new();
+        for base in BASE_LABELS {
+            let span = raw::span(base, position).expect("BASE_LABELS are well-formed");
+            for value in &domain {
+                let label = format!("{}{value}{}", &base[..span.start], &base[span.end..]);
+                disagreements.extend(self.check(&label));
+            }
+        }
+        disagreements
+    }
+
+    /// Generates `count` labels whose fields are all random, and reports the labels the two disagree on.
+    ///
+    /// Each field is `xx` at the probability of 1/8, if the field can be `xx`.
+    /// The result is the same as long as `seed` is the same.
+    pub fn random(&self, seed: u64, count: usize) -> Vec<Disagreement> {
+        let domains: Vec<_> = raw::POSITIONS
+            .iter()
+            .map(|position| self.domain(*position))
+            .collect();
+        // xorshift does not work with zero state
+        let mut state = seed | 1;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+
+        let mut disagreements = Vec::new();
+        for _ in 0..count {
+            let mut domains = domains.iter();
+            // `raw::build` visits the fields in the order of `raw::POSITIONS`.
+            let label = raw::build(|_| {
+                let domain = domains.next().unwrap();
+                let values = match domain.split_last() {
+                    Some((xx, values)) if xx == "xx" => {
+                        if values.is_empty() || next() % 8 == 0 {
+                            return xx.clone();
+                        }
+                        values
+                    }
+                    _ => domain,
+                };
+                values[next() % values.len()].clone()
+            });
+            disagreements.extend(self.check(&label));
+        }
+        disagreements
+    }
+
+    fn check(&self, label: &str) -> Option<Disagreement> {
+        let label: Label = label
+            .parse()
+            .expect("Verifier only generates well-formed labels");
+        let structured = self.structured.test(&label);
+        let regex = self.regex.test(&label);
+        (structured != regex).then_some(Disagreement {
+            label,
+            structured,
+            regex,
+        })
+    }
+
+    /// The values of the field at the position, as they appear in full-context label.
+    ///
+    /// The last value is `xx` if the field can be `xx`.
+    fn domain(&self, position: AllPosition) -> Vec<String> {
+        let mut domain: Vec<String> = match position {
+            AllPosition::Phone(_) => {
+                let mut phonemes: Vec<String> = PHONEMES.iter().map(|p| p.to_string()).collect();
+                if let AllQuestion::Phone(question) = &self.structured {
+                    for phoneme in question.range.iter().flatten() {
+                        // A phoneme with delimiters cannot appear in a label,
+                        // and the label would be split at the wrong place.
+                        if !phonemes.contains(phoneme) && !phoneme.contains(PHONEME_DELIMITERS) {
+                            phonemes.push(phoneme.clone());
+                        }
+                    }
+                }
+                phonemes
+            }
+            AllPosition::SignedRange(_) => (i8::MIN..=i8::MAX).map(|v| v.to_string()).collect(),
+            AllPosition::UnsignedRange(_) => (0..=u8::MAX).map(|v| v.to_string()).collect(),
+            AllPosition::Boolean(_) => vec!["0".to_string(), "1".to_string()],
+            AllPosition::Category(
+                CategoryPosition::B1 | CategoryPosition::C1 | CategoryPosition::D1,
+            ) => (0..100).map(|v| format!("{v:02}")).collect(),
+            AllPosition::Category(_) => (0..10).map(|v| v.to_string()).collect(),
+            AllPosition::Undefined(_) => vec![],
+        };
+        // `K` is never `xx`
+        if !matches!(
+            position,
+            AllPosition::UnsignedRange(
+                UnsignedRangePosition::K1 | UnsignedRangePosition::K2 | UnsignedRangePosition::K3
+            )
+        ) {
+            domain.push("xx".to_string());
+        }
+        domain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Verifier;
+
+    #[test]
+    fn agree() {
+        for patterns in [
+            &["*/A:-??+*", "*/A:-?+*"][..],
+            &["*-5"],
+            &["*_1?/I:*", "*_2?/I:*"],
+            &["*/B:04-*", "*/B:20-*"],
+            &["*^k-*", "*^ky-*"],
+            &["*^xx-*"],
+            &["*!1_*"],
+            &["*-0/F:*"],
+            &["*#xx_*"],
+            &["*^a-b-*"],
+        ] {
+            let verifier = Verifier::new(patterns).unwrap();
+            assert_eq!(verifier.exhaustive(), [], "{patterns:?}");
+            assert_eq!(verifier.random(42, 100), [], "{patterns:?}");
+        }
+    }
+
+    #[test]
+    fn disagree() {
+        // B1 is zero-padded in the label, so `1` never appears.
+        let verifier = Verifier::new(&["*/B:1-*"]).unwrap();
+        let disagreements = verifier.exhaustive();
+        assert_eq!(disagreements.len(), 2);
+        assert!(disagreements.iter().all(|d| d.structured && !d.regex));
+        assert!(
+            disagreements[0]
+                .to_string()
+                .ends_with(": AllQuestion true, regex false")
+        );
+
+        // `?` alone is parsed as 1 to 9, but matches 0 as well in HTS-style matching,
+        // e.g. F2 is 0 for flat accent.
+        let verifier = Verifier::new(&["*_?#*"]).unwrap();
+        let disagreements = verifier.exhaustive();
+        assert_eq!(disagreements.len(), 2);
+        assert!(disagreements.iter().all(|d| !d.structured && d.regex));
+        assert!(
+            disagreements.iter().all(|d| d
+                .label
+                .accent_phrase_curr
+                .as_ref()
+                .unwrap()
+                .accent_position
+                == 0)
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Fallback using wildcard matching
+//!
+//! Unlike [`crate::regex`], this module does not require any feature or dependency.
+
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt::{self, Write};
+
+use jlabel::Label;
+
+use crate::{ParseError, QuestionMatcher};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A pattern using HTS-style wildcards.
+///
+/// `*` matches any sequence of bytes (including empty one), and `?` matches any single byte.
+/// The pattern must match the entire string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct WildcardPattern(String);
+
+impl WildcardPattern {
+    /// Creates a pattern.
+    pub fn new<S: Into<String>>(pattern: S) -> Self {
+        Self(pattern.into())
+    }
+
+    /// The source of the pattern.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Checks if the string matches the pattern.
+    pub fn is_match(&self, s: &str) -> bool {
+        let pattern = self.0.as_bytes();
+        let text = s.as_bytes();
+
+        let (mut p, mut t) = (0, 0);
+        // The position of last `*` in the pattern, and the position in the text it started matching
+        let mut backtrack = None;
+        while t < text.len() {
+            match pattern.get(p) {
+                Some(b'*') => {
+                    p += 1;
+                    backtrack = Some((p, t));
+                }
+                Some(&c) if c == b'?' || c == text[t] => {
+                    p += 1;
+                    t += 1;
+                }
+                _ => match backtrack {
+                    // Let the last `*` consume one more byte
+                    Some((bp, bt)) => {
+                        p = bp;
+                        t = bt + 1;
+                        backtrack = Some((bp, bt + 1));
+                    }
+                    None => return false,
+                },
+            }
+        }
+        pattern[p..].iter().all(|c| *c == b'*')
+    }
+
+    /// Checks if the full-context label matches the pattern,
+    /// without serializing the label into [`String`].
+    pub fn is_match_label(&self, label: &Label) -> bool {
+        Matcher::run(core::slice::from_ref(self), label)
+    }
+}
+
+/// A fallback structure for parsing and checking of question using wildcard matching.
+///
+/// Parsing always succeeds, as any string is a valid wildcard pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct WildcardQuestion(Vec<WildcardPattern>);
+
+impl WildcardQuestion {
+    /// The patterns of this question.
+    pub fn patterns(&self) -> &[WildcardPattern] {
+        &self.0
+    }
+}
+
+impl QuestionMatcher for WildcardQuestion {
+    fn parse<S: AsRef<str>>(patterns: &[S]) -> Result<Self, ParseError> {
+        Ok(Self(
+            patterns
+                .iter()
+                .map(|pattern| WildcardPattern::new(pattern.as_ref()))
+                .collect(),
+        ))
+    }
+    fn test(&self, label: &Label) -> bool {
+        Matcher::run(&self.0, label)
+    }
+    fn test_str(&self, label: &str) -> bool {
+        self.0.iter().any(|pattern| pattern.is_match(label))
+    }
+}
+
+/// Matches the serialized label against patterns byte by byte, while it is being formatted.
+///
+/// Each pattern is simulated as NFA, whose state `i` means
+/// the first `i` bytes of the pattern has matched.
+struct Matcher<'a> {
+    patterns: &'a [WildcardPattern],
+    /// States of all the patterns, each having `pattern.len() + 1` states.
+    states: Vec<bool>,
+    next: Vec<bool>,
+}
+
+impl<'a> Matcher<'a> {
+    fn run(patterns: &'a [WildcardPattern], label: &Label) -> bool {
+        let len = patterns.iter().map(|p| p.0.len() + 1).sum();
+        let mut matcher = Self {
+            patterns,
+            states: vec![false; len],
+            next: vec![false; len],
+        };
+
+        let mut offset = 0;
+        for pattern in patterns {
+            let states = &mut matcher.states[offset..offset + pattern.0.len() + 1];
+            states[0] = true;
+            Self::closure(pattern.0.as_bytes(), states);
+            offset += states.len();
+        }
+
+        // `write!` fails early if none of the patterns can match anymore.
+        let _ = write!(matcher, "{label}");
+
+        let mut offset = 0;
+        patterns.iter().any(|pattern| {
+            offset += pattern.0.len() + 1;
+            matcher.states[offset - 1]
+        })
+    }
+
+    /// Follows the `*`s, which may match empty sequence.
+    fn closure(pattern: &[u8], states: &mut [bool]) {
+        for (i, c) in pattern.iter().enumerate() {
+            if states[i] && *c == b'*' {
+                states[i + 1] = true;
+            }
+        }
+    }
+
+    fn step(&mut self, byte: u8) -> bool {
+        self.next.fill(false);
+        let mut alive = false;
+
+        let mut offset = 0;
+        for pattern in self.patterns {
+            let pattern = pattern.0.as_bytes();
+            let states = &self.states[offset..offset + pattern.len() + 1];
+            let next = &mut self.next[offset..offset + pattern.len() + 1];
+            for (i, c) in pattern.iter().enumerate() {
+                if !states[i] {
+                    continue;
+                }
+                match *c {
+                    b'*' => next[i] = true,
+                    c if c == b'?' || c == byte => next[i + 1] = true,
+                    _ => (),
+                }
+            }
+            Self::closure(pattern, next);
+            alive |= next.iter().any(|s| *s);
+            offset += pattern.len() + 1;
+        }
+
+        core::mem::swap(&mut self.states, &mut self.next);
+        alive
+    }
+}
+
+impl Write for Matcher<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            if !self.step(byte) {
+                return Err(fmt::Error);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_LABEL: &str = "sil^k-o+N=n/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5";
+
+    #[test]
+    fn pattern() {
+        let cases = [
+            ("*", "", true),
+            ("*", "abc", true),
+            ("?", "", false),
+            ("?", "a", true),
+            ("a*c", "abbbc", true),
+            ("a*c", "abbbcd", false),
+            ("a*c*", "abbbcd", true),
+            ("*a?c", "aabc", true),
+            ("*-5", "1+1-5", true),
+            ("*-5", "1+1-50", false),
+            ("**a**", "bab", true),
+            ("a*b*c", "aXbXbXc", true),
+            ("a*b*c", "aXbXbX", false),
+        ];
+        for (pattern, s, expected) in cases {
+            assert_eq!(
+                WildcardPattern::new(pattern).is_match(s),
+                expected,
+                "{pattern} {s}"
+            );
+        }
+    }
+
+    #[test]
+    fn label() {
+        let label: Label = TEST_LABEL.parse().unwrap();
+
+        for (patterns, expected) in [
+            (&["*^k-o+*"][..], true),
+            (&["sil^*"], true),
+            (&["^k-o+*"], false),
+            (&["INVALID?*"], false),
+            (&["*-5"], true),
+            (&["*-50"], false),
+            (&["*/A:-?+*", "*/A:-??+*"], true),
+            (&["*/A:-??+*", "*/A:-?+*"], true),
+            (&["*/A:?+*", "*/A:-??+*"], false),
+            (&[], false),
+        ] {
+            let question = WildcardQuestion::parse(patterns).unwrap();
+            assert_eq!(question.test(&label), expected, "{patterns:?}");
+            assert_eq!(question.test_str(TEST_LABEL), expected, "{patterns:?}");
+        }
+    }
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use jlabel::{Label, arbitrary::seeded_bytes};
+use jlabel_question::{AllQuestion, QuestionMatcher, fuzz::QuestionPatterns};
+
+#[test]
+fn parse_and_test() {
+    let data = seeded_bytes(1, 1 << 16);
+
+    let mut u = Unstructured::new(&data);
+    let mut count = 0;
+    while let (Ok(patterns), Ok(label)) = (
+        QuestionPatterns::arbitrary(&mut u),
+        Label::arbitrary(&mut u),
+    ) {
+        if u.is_empty() {
+            break;
+        }
+        let question = AllQuestion::parse(&patterns.patterns).unwrap();
+        assert_eq!(question.position(), patterns.position, "{patterns:?}");
+
+        let label_str = label.to_string();
+        assert_eq!(
+            question.test_str(&label_str),
+            question.test(&label),
+            "{patterns:?} {label_str}"
+        );
+        count += 1;
+    }
+    assert!(count > 100);
+}
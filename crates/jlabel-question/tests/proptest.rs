@@ -0,0 +1,16 @@
+#![cfg(feature = "proptest")]
+
+use jlabel::Label;
+use jlabel_question::{AllQuestion, QuestionMatcher, fuzz::QuestionPatterns};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn parse_and_test(patterns: QuestionPatterns, label: Label) {
+        let question = AllQuestion::parse(&patterns.patterns).unwrap();
+        prop_assert_eq!(question.position(), patterns.position);
+
+        let label_str = label.to_string();
+        prop_assert_eq!(question.test_str(&label_str), question.test(&label));
+    }
+}
@@ -61,3 +61,95 @@ fn parse_range_from_name_i8(name: &str) -> Option<Range<i8>> {
         None
     }
 }
+
+#[test]
+fn lint_all_questions() {
+    use jlabel_question::lint::{EntryKind, QsEntry, WarningKind, lint};
+
+    let source = std::fs::read_to_string("tests/questions.hed").unwrap();
+    let entries: Vec<_> = source.lines().filter_map(QsEntry::parse).collect();
+    let reports = lint(&entries);
+    assert_eq!(reports.len(), entries.len());
+
+    for report in &reports {
+        assert_eq!(report.kind, EntryKind::Structured, "{}", report.name);
+        for warning in &report.warnings {
+            assert!(
+                matches!(
+                    warning.kind,
+                    WarningKind::Duplicate { .. } | WarningKind::Overlap { .. }
+                ),
+                "{}: {warning}",
+                report.name
+            );
+        }
+    }
+
+    // `Utt_Len_Acc=10` appears twice in the file
+    let first = entries
+        .iter()
+        .position(|entry| entry.name == "Utt_Len_Acc=10")
+        .unwrap();
+    let second = first
+        + 1
+        + entries[first + 1..]
+            .iter()
+            .position(|entry| entry.name == "Utt_Len_Acc=10")
+            .unwrap();
+    assert_eq!(
+        reports[second].warnings[0].kind,
+        WarningKind::Duplicate { entry: first }
+    );
+}
+
+#[test]
+fn test_str_all_questions() {
+    use jlabel::Label;
+    use jlabel_question::lint::QsEntry;
+
+    const LABELS: [&str; 4] = [
+        "xx^xx-sil+k=o/A:xx+xx+xx/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:xx_xx#xx_xx@xx_xx|xx_xx/G:5_5%0_xx_xx/H:xx_xx/I:xx-xx@xx+xx&xx-xx|xx+xx/J:1_5/K:1+1-5",
+        "sil^k-o+N=n/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5",
+        "w^a-sil+xx=xx/A:xx+xx+xx/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:5_5!0_xx-xx/F:xx_xx#xx_xx@xx_xx|xx_xx/G:xx_xx%xx_xx_xx/H:1_5/I:xx-xx@xx+xx&xx-xx|xx+xx/J:xx_xx/K:1+1-5",
+        "n^i-o+i=cl/A:2+3+1/B:04-xx_xx/C:13_xx+xx/D:20+1_1/E:xx_xx!xx_xx-xx/F:3_1#0_xx@1_4|1_12/G:3_3%0_xx_1/H:xx_xx/I:4-12@1+2&1-6|1+21/J:2_9/K:2+6-21",
+    ];
+    let labels: Vec<Label> = LABELS.iter().map(|s| s.parse().unwrap()).collect();
+
+    let source = std::fs::read_to_string("tests/questions.hed").unwrap();
+    for entry in source.lines().filter_map(QsEntry::parse) {
+        let question = AllQuestion::parse(&entry.patterns).unwrap();
+        for (label_str, label) in LABELS.iter().zip(&labels) {
+            assert_eq!(
+                question.test_str(label_str),
+                question.test(label),
+                "{} {label_str}",
+                entry.name
+            );
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn verify_all_questions() {
+    use jlabel_question::{
+        lint::QsEntry,
+        verify::{Disagreement, Verifier},
+    };
+
+    let source = std::fs::read_to_string("tests/questions.hed").unwrap();
+    // Verifying all the entries takes too long
+    for entry in source.lines().filter_map(QsEntry::parse).step_by(20) {
+        let question = AllQuestion::parse(&entry.patterns).unwrap();
+        // `?` alone is parsed as 1 to 9, but matches 0 as well in HTS-style matching.
+        let known = |d: &&Disagreement| {
+            !d.structured && d.regex && question.explain(&d.label).value.as_deref() == Some("0")
+        };
+
+        let verifier = Verifier::new(&entry.patterns).unwrap();
+        for disagreements in [verifier.exhaustive(), verifier.random(1, 20)] {
+            let unknown: Vec<_> = disagreements.iter().filter(|d| !known(d)).collect();
+            assert!(unknown.is_empty(), "{}: {}", entry.name, unknown[0]);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Implementations of [`Arbitrary`] generating structurally valid labels
+//!
+//! The generated labels are serialized and parsed back into the same labels.
+//!
+//! Requires `arbitrary` feature.
+//!
+//! ```rust
+//! use arbitrary::{Arbitrary, Unstructured};
+//! use jlabel::{Label, arbitrary::seeded_bytes};
+//!
+//! let data = seeded_bytes(1, 1024);
+//! let label = Label::arbitrary(&mut Unstructured::new(&data)).unwrap();
+//! assert_eq!(label.to_string().parse::<Label>().unwrap(), label);
+//! ```
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{
+    AccentPhraseCurrent, AccentPhrasePrevNext, BreathGroupCurrent, BreathGroupPrevNext, Label,
+    Mora, Phoneme, Utterance, Word,
+};
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Deterministic bytes for [`Unstructured`], generated by xorshift from `seed`.
+///
+/// This is for running the [`Arbitrary`] implementations in tests without a fuzzer.
+/// `seed` must not be zero.
+pub fn seeded_bytes(seed: u32, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
+
+/// Phoneme of 1 to 3 ASCII letters except for `xx`, which means empty.
+pub fn phoneme(u: &mut Unstructured<'_>) -> Result<String> {
+    let len = u.int_in_range(1..=3)?;
+    let mut phoneme = String::with_capacity(len);
+    for _ in 0..len {
+        phoneme.push(char::from(*u.choose(LETTERS)?));
+    }
+    if phoneme == "xx" {
+        phoneme.truncate(1);
+    }
+    Ok(phoneme)
+}
+
+fn phoneme_or_xx(u: &mut Unstructured<'_>) -> Result<Option<String>> {
+    if !bool::arbitrary(u)? {
+        return Ok(None);
+    }
+    phoneme(u).map(Some)
+}
+
+impl<'a> Arbitrary<'a> for Phoneme {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            p2: phoneme_or_xx(u)?,
+            p1: phoneme_or_xx(u)?,
+            c: phoneme_or_xx(u)?,
+            n1: phoneme_or_xx(u)?,
+            n2: phoneme_or_xx(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Mora {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            relative_accent_position: u.arbitrary()?,
+            position_forward: u.arbitrary()?,
+            position_backward: u.arbitrary()?,
+        })
+    }
+}
+
+fn code(u: &mut Unstructured<'_>, max: u8) -> Result<Option<u8>> {
+    if !bool::arbitrary(u)? {
+        return Ok(None);
+    }
+    Ok(Some(u.int_in_range(0..=max)?))
+}
+
+impl<'a> Arbitrary<'a> for Word {
+    /// POS is in two digits and the others are in a digit, as they are in the label.
+    /// At least one of the fields is not `None`, as `xx_xx_xx` is parsed as no word.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut word = Self {
+            pos: code(u, 99)?,
+            ctype: code(u, 9)?,
+            cform: code(u, 9)?,
+        };
+        if word.pos.is_none() && word.ctype.is_none() && word.cform.is_none() {
+            word.pos = Some(u.int_in_range(0..=99)?);
+        }
+        Ok(word)
+    }
+}
+
+impl<'a> Arbitrary<'a> for AccentPhraseCurrent {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            mora_count: u.arbitrary()?,
+            accent_position: u.arbitrary()?,
+            is_interrogative: u.arbitrary()?,
+            accent_phrase_position_forward: u.arbitrary()?,
+            accent_phrase_position_backward: u.arbitrary()?,
+            mora_position_forward: u.arbitrary()?,
+            mora_position_backward: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for AccentPhrasePrevNext {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            mora_count: u.arbitrary()?,
+            accent_position: u.arbitrary()?,
+            is_interrogative: u.arbitrary()?,
+            is_pause_insertion: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for BreathGroupCurrent {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            accent_phrase_count: u.arbitrary()?,
+            mora_count: u.arbitrary()?,
+            breath_group_position_forward: u.arbitrary()?,
+            breath_group_position_backward: u.arbitrary()?,
+            accent_phrase_position_forward: u.arbitrary()?,
+            accent_phrase_position_backward: u.arbitrary()?,
+            mora_position_forward: u.arbitrary()?,
+            mora_position_backward: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for BreathGroupPrevNext {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            accent_phrase_count: u.arbitrary()?,
+            mora_count: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Utterance {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            breath_group_count: u.arbitrary()?,
+            accent_phrase_count: u.arbitrary()?,
+            mora_count: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Label {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            phoneme: u.arbitrary()?,
+            mora: u.arbitrary()?,
+            word_prev: u.arbitrary()?,
+            word_curr: u.arbitrary()?,
+            word_next: u.arbitrary()?,
+            accent_phrase_prev: u.arbitrary()?,
+            accent_phrase_curr: u.arbitrary()?,
+            accent_phrase_next: u.arbitrary()?,
+            breath_group_prev: u.arbitrary()?,
+            breath_group_curr: u.arbitrary()?,
+            breath_group_next: u.arbitrary()?,
+            utterance: u.arbitrary()?,
+        })
+    }
+}
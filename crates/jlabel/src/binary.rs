@@ -0,0 +1,428 @@
+//! Compact binary encoding of label sequences
+//!
+//! The encoding is a header, a table of the phonemes, and fixed-width records of the labels.
+//! [`LabelsView`] reads the records in place, so the encoded bytes can be memory-mapped
+//! and the labels are decoded only when accessed.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::{Label, binary::{LabelsView, decode, encode}};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let labels: Vec<Label> = [
+//!     "xx^xx-sil+k=o/A:xx+xx+xx/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:xx_xx#xx_xx@xx_xx|xx_xx/G:2_1%0_xx_xx/H:xx_xx/I:xx-xx@xx+xx&xx-xx|xx+xx/J:1_2/K:1+1-2",
+//!     "xx^sil-k+o=sil/A:0+1+2/B:xx-xx_xx/C:02_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:2_1#0_xx@1_1|1_2/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-2@1+1&1-1|1+2/J:xx_xx/K:1+1-2",
+//! ]
+//! .iter()
+//! .map(|s| s.parse())
+//! .collect::<Result<_, _>>()?;
+//!
+//! let bytes = encode(&labels)?;
+//! assert_eq!(decode(&bytes)?, labels);
+//!
+//! let view = LabelsView::new(&bytes)?;
+//! assert_eq!(view.len(), 2);
+//! assert_eq!(view.phonemes(), ["sil", "k", "o"]);
+//! assert_eq!(view.get(1)?, Some(labels[1].clone()));
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+//!
+//! # Layout
+//!
+//! All integers are little-endian.
+//!
+//! | Offset | Size | Content |
+//! |---|---|---|
+//! | 0 | 4 | Magic `JLBL` |
+//! | 4 | 2 | Version ([`VERSION`]) |
+//! | 6 | 2 | Reserved (0) |
+//! | 8 | 4 | Number of phonemes |
+//! | 12 | 4 | Number of labels |
+//! | 16 | | Phonemes, each of which is the length in bytes (1 byte) followed by UTF-8 |
+//! | | | Padding to a multiple of 8 bytes |
+//! | | 56 each | Labels |
+//!
+//! A label is encoded as the following fields in this order:
+//!
+//! - P1 to P5: the indices of the phonemes (2 bytes each), `0xFFFF` if undefined
+//! - The flags which blocks and fields are defined (4 bytes)
+//! - A1 to K3 except E4, F4 and G4 (1 byte each), `0` if undefined
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    AccentPhraseCurrent, AccentPhrasePrevNext, BreathGroupCurrent, BreathGroupPrevNext, Label,
+    Mora, Phoneme, Utterance, Word,
+};
+
+/// The version of the encoding.
+pub const VERSION: u16 = 1;
+
+const MAGIC: &[u8; 4] = b"JLBL";
+const HEADER_SIZE: usize = 16;
+const RECORD_SIZE: usize = 56;
+const UNDEFINED_PHONEME: u16 = u16::MAX;
+
+// The flags which blocks and fields are defined
+const MORA: u32 = 1 << 0;
+const WORD_PREV: u32 = 1 << 1;
+const WORD_CURR: u32 = 1 << 4;
+const WORD_NEXT: u32 = 1 << 7;
+const ACCENT_PHRASE_PREV: u32 = 1 << 10;
+const ACCENT_PHRASE_PREV_PAUSE: u32 = 1 << 11;
+const ACCENT_PHRASE_CURR: u32 = 1 << 12;
+const ACCENT_PHRASE_NEXT: u32 = 1 << 13;
+const ACCENT_PHRASE_NEXT_PAUSE: u32 = 1 << 14;
+const BREATH_GROUP_PREV: u32 = 1 << 15;
+const BREATH_GROUP_CURR: u32 = 1 << 16;
+const BREATH_GROUP_NEXT: u32 = 1 << 17;
+
+/// Errors from encoding and decoding.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BinaryError {
+    /// The bytes do not start with the magic.
+    #[error("Not a binary label")]
+    InvalidMagic,
+    /// The version is not supported.
+    #[error("Unsupported version {0}")]
+    UnsupportedVersion(u16),
+    /// The bytes end before the end of the content.
+    #[error("Unexpected end of bytes")]
+    UnexpectedEnd,
+    /// A phoneme is not valid UTF-8.
+    #[error("Phoneme {0} is not valid UTF-8")]
+    InvalidPhoneme(usize),
+    /// A label refers to a phoneme not in the table.
+    #[error("Label {0} refers to unknown phoneme")]
+    UnknownPhoneme(usize),
+    /// There are too many kinds of phonemes to encode.
+    #[error("Too many phonemes")]
+    TooManyPhonemes,
+    /// A phoneme is too long to encode.
+    #[error("Phoneme {0:?} is longer than 255 bytes")]
+    PhonemeTooLong(String),
+    /// There are too many labels to encode.
+    #[error("Too many labels")]
+    TooManyLabels,
+}
+
+/// Encodes the labels.
+pub fn encode(labels: &[Label]) -> Result<Vec<u8>, BinaryError> {
+    let label_count = u32::try_from(labels.len()).map_err(|_| BinaryError::TooManyLabels)?;
+
+    let mut phonemes: Vec<&str> = Vec::new();
+    let mut ids: BTreeMap<&str, u16> = BTreeMap::new();
+    let mut records = Vec::with_capacity(labels.len() * RECORD_SIZE);
+    for label in labels {
+        let phoneme = &label.phoneme;
+        for p in [
+            &phoneme.p2,
+            &phoneme.p1,
+            &phoneme.c,
+            &phoneme.n1,
+            &phoneme.n2,
+        ] {
+            let id = match p.as_deref() {
+                None => UNDEFINED_PHONEME,
+                Some(p) => match ids.get(p) {
+                    Some(id) => *id,
+                    None if phonemes.len() < UNDEFINED_PHONEME as usize => {
+                        if p.len() > u8::MAX as usize {
+                            return Err(BinaryError::PhonemeTooLong(p.to_string()));
+                        }
+                        let id = phonemes.len() as u16;
+                        phonemes.push(p);
+                        ids.insert(p, id);
+                        id
+                    }
+                    None => return Err(BinaryError::TooManyPhonemes),
+                },
+            };
+            records.extend(id.to_le_bytes());
+        }
+        encode_fields(label, &mut records);
+    }
+
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + records.len() + phonemes.len() * 4);
+    bytes.extend(MAGIC);
+    bytes.extend(VERSION.to_le_bytes());
+    bytes.extend(0u16.to_le_bytes());
+    bytes.extend((phonemes.len() as u32).to_le_bytes());
+    bytes.extend(label_count.to_le_bytes());
+    for phoneme in phonemes {
+        bytes.push(phoneme.len() as u8);
+        bytes.extend(phoneme.as_bytes());
+    }
+    bytes.resize(bytes.len().next_multiple_of(8), 0);
+    bytes.extend(records);
+    Ok(bytes)
+}
+
+/// Decodes all the labels.
+pub fn decode(bytes: &[u8]) -> Result<Vec<Label>, BinaryError> {
+    let view = LabelsView::new(bytes)?;
+    (0..view.len()).map(|index| view.try_get(index)).collect()
+}
+
+fn encode_fields(label: &Label, record: &mut Vec<u8>) {
+    let mut flags = 0;
+    let mut fields = [0u8; 39];
+
+    if let Some(mora) = &label.mora {
+        flags |= MORA;
+        fields[0] = mora.relative_accent_position as u8;
+        fields[1] = mora.position_forward;
+        fields[2] = mora.position_backward;
+    }
+    for (offset, flag, word) in [
+        (3, WORD_PREV, &label.word_prev),
+        (6, WORD_CURR, &label.word_curr),
+        (9, WORD_NEXT, &label.word_next),
+    ] {
+        let Some(word) = word else { continue };
+        for (i, value) in [word.pos, word.ctype, word.cform].into_iter().enumerate() {
+            if let Some(value) = value {
+                flags |= flag << i;
+                fields[offset + i] = value;
+            }
+        }
+    }
+    for (offset, flag, pause_flag, phrase) in [
+        (
+            12,
+            ACCENT_PHRASE_PREV,
+            ACCENT_PHRASE_PREV_PAUSE,
+            &label.accent_phrase_prev,
+        ),
+        (
+            23,
+            ACCENT_PHRASE_NEXT,
+            ACCENT_PHRASE_NEXT_PAUSE,
+            &label.accent_phrase_next,
+        ),
+    ] {
+        let Some(phrase) = phrase else { continue };
+        flags |= flag;
+        fields[offset] = phrase.mora_count;
+        fields[offset + 1] = phrase.accent_position;
+        fields[offset + 2] = phrase.is_interrogative.into();
+        if let Some(is_pause_insertion) = phrase.is_pause_insertion {
+            flags |= pause_flag;
+            fields[offset + 3] = is_pause_insertion.into();
+        }
+    }
+    if let Some(phrase) = &label.accent_phrase_curr {
+        flags |= ACCENT_PHRASE_CURR;
+        fields[16..23].copy_from_slice(&[
+            phrase.mora_count,
+            phrase.accent_position,
+            phrase.is_interrogative.into(),
+            phrase.accent_phrase_position_forward,
+            phrase.accent_phrase_position_backward,
+            phrase.mora_position_forward,
+            phrase.mora_position_backward,
+        ]);
+    }
+    for (offset, flag, group) in [
+        (27, BREATH_GROUP_PREV, &label.breath_group_prev),
+        (37, BREATH_GROUP_NEXT, &label.breath_group_next),
+    ] {
+        let Some(group) = group else { continue };
+        flags |= flag;
+        fields[offset] = group.accent_phrase_count;
+        fields[offset + 1] = group.mora_count;
+    }
+    if let Some(group) = &label.breath_group_curr {
+        flags |= BREATH_GROUP_CURR;
+        fields[29..37].copy_from_slice(&[
+            group.accent_phrase_count,
+            group.mora_count,
+            group.breath_group_position_forward,
+            group.breath_group_position_backward,
+            group.accent_phrase_position_forward,
+            group.accent_phrase_position_backward,
+            group.mora_position_forward,
+            group.mora_position_backward,
+        ]);
+    }
+
+    record.extend(flags.to_le_bytes());
+    record.extend(fields);
+    let utterance = &label.utterance;
+    record.extend([
+        utterance.breath_group_count,
+        utterance.accent_phrase_count,
+        utterance.mora_count,
+    ]);
+}
+
+/// The encoded labels read in place.
+#[derive(Debug, Clone)]
+pub struct LabelsView<'a> {
+    phonemes: Vec<&'a str>,
+    records: &'a [u8],
+}
+
+impl<'a> LabelsView<'a> {
+    /// Reads the header and the phonemes of the encoded labels.
+    ///
+    /// The labels are decoded in [`LabelsView::get`].
+    pub fn new(bytes: &'a [u8]) -> Result<Self, BinaryError> {
+        let header = bytes.get(..HEADER_SIZE).ok_or(BinaryError::UnexpectedEnd)?;
+        if &header[0..4] != MAGIC {
+            return Err(BinaryError::InvalidMagic);
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != VERSION {
+            return Err(BinaryError::UnsupportedVersion(version));
+        }
+        let phoneme_count = u32::from_le_bytes(header[8..12].try_into().unwrap()) as usize;
+        let label_count = u32::from_le_bytes(header[12..16].try_into().unwrap()) as usize;
+
+        let mut offset = HEADER_SIZE;
+        let mut phonemes = Vec::with_capacity(phoneme_count.min(UNDEFINED_PHONEME as usize));
+        for index in 0..phoneme_count {
+            let len = *bytes.get(offset).ok_or(BinaryError::UnexpectedEnd)? as usize;
+            let phoneme = bytes
+                .get(offset + 1..offset + 1 + len)
+                .ok_or(BinaryError::UnexpectedEnd)?;
+            phonemes.push(
+                core::str::from_utf8(phoneme).map_err(|_| BinaryError::InvalidPhoneme(index))?,
+            );
+            offset += 1 + len;
+        }
+
+        let start = offset.next_multiple_of(8);
+        let records = label_count
+            .checked_mul(RECORD_SIZE)
+            .and_then(|len| bytes.get(start..start.checked_add(len)?))
+            .ok_or(BinaryError::UnexpectedEnd)?;
+
+        Ok(Self { phonemes, records })
+    }
+
+    /// The number of the labels.
+    pub fn len(&self) -> usize {
+        self.records.len() / RECORD_SIZE
+    }
+
+    /// Returns `true` if there are no labels.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// The phonemes in the table.
+    pub fn phonemes(&self) -> &[&'a str] {
+        &self.phonemes
+    }
+
+    /// Decodes the label at the index.
+    ///
+    /// Returns `Ok(None)` if the index is out of range,
+    /// and an error if the label refers to an unknown phoneme.
+    pub fn get(&self, index: usize) -> Result<Option<Label>, BinaryError> {
+        if index >= self.len() {
+            return Ok(None);
+        }
+        self.try_get(index).map(Some)
+    }
+
+    /// Decodes all the labels in order.
+    pub fn iter(&self) -> impl Iterator<Item = Result<Label, BinaryError>> + '_ {
+        (0..self.len()).map(|index| self.try_get(index))
+    }
+
+    fn try_get(&self, index: usize) -> Result<Label, BinaryError> {
+        let record = self
+            .records
+            .get(index * RECORD_SIZE..(index + 1) * RECORD_SIZE)
+            .ok_or(BinaryError::UnexpectedEnd)?;
+
+        let mut phonemes = [const { None }; 5];
+        for (i, phoneme) in phonemes.iter_mut().enumerate() {
+            let id = u16::from_le_bytes([record[i * 2], record[i * 2 + 1]]);
+            if id != UNDEFINED_PHONEME {
+                let p = self
+                    .phonemes
+                    .get(id as usize)
+                    .ok_or(BinaryError::UnknownPhoneme(index))?;
+                *phoneme = Some(p.to_string());
+            }
+        }
+        let [p2, p1, c, n1, n2] = phonemes;
+
+        let flags = u32::from_le_bytes(record[10..14].try_into().unwrap());
+        let f = &record[14..];
+        let has = |flag: u32| flags & flag != 0;
+
+        let word = |offset: usize, flag: u32| {
+            let field = |i: usize| has(flag << i).then_some(f[offset + i]);
+            let word = Word {
+                pos: field(0),
+                ctype: field(1),
+                cform: field(2),
+            };
+            (word.pos.is_some() || word.ctype.is_some() || word.cform.is_some()).then_some(word)
+        };
+        let accent_phrase = |offset: usize, flag: u32, pause_flag: u32| {
+            has(flag).then(|| AccentPhrasePrevNext {
+                mora_count: f[offset],
+                accent_position: f[offset + 1],
+                is_interrogative: f[offset + 2] != 0,
+                is_pause_insertion: has(pause_flag).then_some(f[offset + 3] != 0),
+            })
+        };
+        let breath_group = |offset: usize, flag: u32| {
+            has(flag).then(|| BreathGroupPrevNext {
+                accent_phrase_count: f[offset],
+                mora_count: f[offset + 1],
+            })
+        };
+
+        Ok(Label {
+            phoneme: Phoneme { p2, p1, c, n1, n2 },
+            mora: has(MORA).then(|| Mora {
+                relative_accent_position: f[0] as i8,
+                position_forward: f[1],
+                position_backward: f[2],
+            }),
+            word_prev: word(3, WORD_PREV),
+            word_curr: word(6, WORD_CURR),
+            word_next: word(9, WORD_NEXT),
+            accent_phrase_prev: accent_phrase(12, ACCENT_PHRASE_PREV, ACCENT_PHRASE_PREV_PAUSE),
+            accent_phrase_curr: has(ACCENT_PHRASE_CURR).then(|| AccentPhraseCurrent {
+                mora_count: f[16],
+                accent_position: f[17],
+                is_interrogative: f[18] != 0,
+                accent_phrase_position_forward: f[19],
+                accent_phrase_position_backward: f[20],
+                mora_position_forward: f[21],
+                mora_position_backward: f[22],
+            }),
+            accent_phrase_next: accent_phrase(23, ACCENT_PHRASE_NEXT, ACCENT_PHRASE_NEXT_PAUSE),
+            breath_group_prev: breath_group(27, BREATH_GROUP_PREV),
+            breath_group_curr: has(BREATH_GROUP_CURR).then(|| BreathGroupCurrent {
+                accent_phrase_count: f[29],
+                mora_count: f[30],
+                breath_group_position_forward: f[31],
+                breath_group_position_backward: f[32],
+                accent_phrase_position_forward: f[33],
+                accent_phrase_position_backward: f[34],
+                mora_position_forward: f[35],
+                mora_position_backward: f[36],
+            }),
+            breath_group_next: breath_group(37, BREATH_GROUP_NEXT),
+            utterance: Utterance {
+                breath_group_count: f[39],
+                accent_phrase_count: f[40],
+                mora_count: f[41],
+            },
+        })
+    }
+}
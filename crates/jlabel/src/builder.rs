@@ -0,0 +1,456 @@
+//! Builder of [`Label`]
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::Label;
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let label = Label::builder()
+//!     .p1("xx").p2("sil").p3("k").p4("o").p5("N")
+//!     .a1(-4).a2(1).a3(5)
+//!     .c1(9)
+//!     .f1(5).f2(5).f3(false).f5(1).f6(1).f7(1).f8(5)
+//!     .i1(1).i2(5).i3(1).i4(1).i5(1).i6(1).i7(1).i8(5)
+//!     .k1(1).k2(1).k3(5)
+//!     .build()?;
+//! assert_eq!(
+//!     label.to_string(),
+//!     "xx^sil-k+o=N/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5"
+//! );
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use alloc::string::String;
+
+use crate::fullcontext_label::{
+    AccentPhraseCurrent, AccentPhrasePrevNext, BreathGroupCurrent, BreathGroupPrevNext, Label,
+    Mora, Phoneme, Utterance, Word,
+};
+
+/// Errors from [`LabelBuilder::build`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BuildError {
+    /// The field is required, because other fields in the same block are set,
+    /// or it is in `K`, which is never undefined.
+    #[error("Missing field {0}")]
+    Missing(&'static str),
+    /// The fields are not consistent with each other.
+    #[error("Inconsistent fields: {rule} ({left} != {right})")]
+    Inconsistent {
+        /// The rule that the fields violate, e.g. `A2 + A3 - 1 = F1`.
+        rule: &'static str,
+        /// The value of the left-hand side.
+        left: i16,
+        /// The value of the right-hand side.
+        right: i16,
+    },
+}
+
+macro_rules! setters {
+    ($($field:ident: $ty:ty, $doc:literal;)*) => {
+        $(
+            #[doc = $doc]
+            pub fn $field(mut self, value: $ty) -> Self {
+                self.$field = Some(value);
+                self
+            }
+        )*
+    };
+}
+
+macro_rules! phoneme_setters {
+    ($($field:ident, $doc:literal;)*) => {
+        $(
+            #[doc = $doc]
+            ///
+            /// `xx` is regarded as undefined.
+            pub fn $field(mut self, value: impl Into<String>) -> Self {
+                self.$field = Some(value.into()).filter(|value| value != "xx");
+                self
+            }
+        )*
+    };
+}
+
+/// Builder of [`Label`], with setters named after the fields of full-context label.
+///
+/// All the fields are undefined (`xx`) by default.
+/// A block (e.g. `A`) is defined if any of its fields is set,
+/// and then all of its fields except for `B`, `C`, `D`, `E5` and `G5` are required.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelBuilder {
+    p1: Option<String>,
+    p2: Option<String>,
+    p3: Option<String>,
+    p4: Option<String>,
+    p5: Option<String>,
+    a1: Option<i8>,
+    a2: Option<u8>,
+    a3: Option<u8>,
+    b1: Option<u8>,
+    b2: Option<u8>,
+    b3: Option<u8>,
+    c1: Option<u8>,
+    c2: Option<u8>,
+    c3: Option<u8>,
+    d1: Option<u8>,
+    d2: Option<u8>,
+    d3: Option<u8>,
+    e1: Option<u8>,
+    e2: Option<u8>,
+    e3: Option<bool>,
+    e5: Option<bool>,
+    f1: Option<u8>,
+    f2: Option<u8>,
+    f3: Option<bool>,
+    f5: Option<u8>,
+    f6: Option<u8>,
+    f7: Option<u8>,
+    f8: Option<u8>,
+    g1: Option<u8>,
+    g2: Option<u8>,
+    g3: Option<bool>,
+    g5: Option<bool>,
+    h1: Option<u8>,
+    h2: Option<u8>,
+    i1: Option<u8>,
+    i2: Option<u8>,
+    i3: Option<u8>,
+    i4: Option<u8>,
+    i5: Option<u8>,
+    i6: Option<u8>,
+    i7: Option<u8>,
+    i8: Option<u8>,
+    j1: Option<u8>,
+    j2: Option<u8>,
+    k1: Option<u8>,
+    k2: Option<u8>,
+    k3: Option<u8>,
+}
+
+impl Label {
+    /// Creates a builder of label.
+    pub fn builder() -> LabelBuilder {
+        LabelBuilder::default()
+    }
+}
+
+impl LabelBuilder {
+    phoneme_setters! {
+        p1, "P1: the phoneme identity before the previous phoneme";
+        p2, "P2: the previous phoneme identity";
+        p3, "P3: the current phoneme identity";
+        p4, "P4: the next phoneme identity";
+        p5, "P5: the phoneme after the next phoneme identity";
+    }
+
+    setters! {
+        a1: i8, "A1: the difference between accent type and position of the current mora identity";
+        a2: u8, "A2: position of the current mora identity in the current accent phrase (forward)";
+        a3: u8, "A3: position of the current mora identity in the current accent phrase (backward)";
+        b1: u8, "B1: pos (part-of-speech) of the previous word";
+        b2: u8, "B2: conjugation type of the previous word";
+        b3: u8, "B3: inflected forms of the previous word";
+        c1: u8, "C1: pos (part-of-speech) of the current word";
+        c2: u8, "C2: conjugation type of the current word";
+        c3: u8, "C3: inflected forms of the current word";
+        d1: u8, "D1: pos (part-of-speech) of the next word";
+        d2: u8, "D2: conjugation type of the next word";
+        d3: u8, "D3: inflected forms of the next word";
+        e1: u8, "E1: the number of moras in the previous accent phrase";
+        e2: u8, "E2: accent type in the previous accent phrase";
+        e3: bool, "E3: whether the previous accent phrase interrogative or not";
+        e5: bool, "E5: whether pause insertion or not in between the previous accent phrase and the current accent phrase, as in the label, i.e. the inverse of [`AccentPhrasePrevNext::is_pause_insertion`]";
+        f1: u8, "F1: the number of moras in the current accent phrase";
+        f2: u8, "F2: accent type in the current accent phrase";
+        f3: bool, "F3: whether the current accent phrase interrogative or not";
+        f5: u8, "F5: position of the current accent phrase identity in the current breath group by the accent phrase (forward)";
+        f6: u8, "F6: position of the current accent phrase identity in the current breath group by the accent phrase (backward)";
+        f7: u8, "F7: position of the current accent phrase identity in the current breath group by the mora (forward)";
+        f8: u8, "F8: position of the current accent phrase identity in the current breath group by the mora (backward)";
+        g1: u8, "G1: the number of moras in the next accent phrase";
+        g2: u8, "G2: accent type in the next accent phrase";
+        g3: bool, "G3: whether the next accent phrase interrogative or not";
+        g5: bool, "G5: whether pause insertion or not in between the next accent phrase and the current accent phrase, as in the label, i.e. the inverse of [`AccentPhrasePrevNext::is_pause_insertion`]";
+        h1: u8, "H1: the number of accent phrases in the previous breath group";
+        h2: u8, "H2: the number of moras in the previous breath group";
+        i1: u8, "I1: the number of accent phrases in the current breath group";
+        i2: u8, "I2: the number of moras in the current breath group";
+        i3: u8, "I3: position of the current breath group identity by breath group (forward)";
+        i4: u8, "I4: position of the current breath group identity by breath group (backward)";
+        i5: u8, "I5: position of the current breath group identity by accent phrase (forward)";
+        i6: u8, "I6: position of the current breath group identity by accent phrase (backward)";
+        i7: u8, "I7: position of the current breath group identity by mora (forward)";
+        i8: u8, "I8: position of the current breath group identity by mora (backward)";
+        j1: u8, "J1: the number of accent phrases in the next breath group";
+        j2: u8, "J2: the number of moras in the next breath group";
+        k1: u8, "K1: the number of breath groups in this utterance";
+        k2: u8, "K2: the number of accent phrases in this utterance";
+        k3: u8, "K3: the number of moras in this utterance";
+    }
+
+    /// Builds the label, and validates the consistency of the fields in it.
+    ///
+    /// The following rules are checked if the fields in them are defined:
+    ///
+    /// - `A1 = A2 - F2`
+    /// - `A2 + A3 - 1 = F1`
+    /// - `F5 + F6 - 1 = I1`
+    /// - `F7 + F8 - 1 = I2`
+    /// - `I3 + I4 - 1 = K1`
+    /// - `I5 + I6 - 1 = K2`
+    /// - `I7 + I8 - 1 = K3`
+    pub fn build(self) -> Result<Label, BuildError> {
+        let label = self.assemble()?;
+        validate(&label)?;
+        Ok(label)
+    }
+
+    fn assemble(self) -> Result<Label, BuildError> {
+        let phoneme = Phoneme {
+            p2: self.p1,
+            p1: self.p2,
+            c: self.p3,
+            n1: self.p4,
+            n2: self.p5,
+        };
+
+        let mora = match (self.a1, self.a2, self.a3) {
+            (Some(a1), Some(a2), Some(a3)) => Some(Mora {
+                relative_accent_position: a1,
+                position_forward: a2,
+                position_backward: a3,
+            }),
+            (None, None, None) => None,
+            (a1, a2, a3) => {
+                return Err(missing(&[
+                    ("A1", a1.is_some()),
+                    ("A2", a2.is_some()),
+                    ("A3", a3.is_some()),
+                ]));
+            }
+        };
+
+        let word = |pos, ctype, cform| match (pos, ctype, cform) {
+            (None, None, None) => None,
+            (pos, ctype, cform) => Some(Word { pos, ctype, cform }),
+        };
+
+        let accent_phrase_prev = match (self.e1, self.e2, self.e3, self.e5) {
+            (Some(e1), Some(e2), Some(e3), e5) => Some(AccentPhrasePrevNext {
+                mora_count: e1,
+                accent_position: e2,
+                is_interrogative: e3,
+                // E5/G5's logics are inverted
+                is_pause_insertion: e5.map(|e5| !e5),
+            }),
+            (None, None, None, None) => None,
+            (e1, e2, e3, _) => {
+                return Err(missing(&[
+                    ("E1", e1.is_some()),
+                    ("E2", e2.is_some()),
+                    ("E3", e3.is_some()),
+                ]));
+            }
+        };
+
+        let accent_phrase_curr = match (
+            self.f1, self.f2, self.f3, self.f5, self.f6, self.f7, self.f8,
+        ) {
+            (Some(f1), Some(f2), Some(f3), Some(f5), Some(f6), Some(f7), Some(f8)) => {
+                Some(AccentPhraseCurrent {
+                    mora_count: f1,
+                    accent_position: f2,
+                    is_interrogative: f3,
+                    accent_phrase_position_forward: f5,
+                    accent_phrase_position_backward: f6,
+                    mora_position_forward: f7,
+                    mora_position_backward: f8,
+                })
+            }
+            (None, None, None, None, None, None, None) => None,
+            (f1, f2, f3, f5, f6, f7, f8) => {
+                return Err(missing(&[
+                    ("F1", f1.is_some()),
+                    ("F2", f2.is_some()),
+                    ("F3", f3.is_some()),
+                    ("F5", f5.is_some()),
+                    ("F6", f6.is_some()),
+                    ("F7", f7.is_some()),
+                    ("F8", f8.is_some()),
+                ]));
+            }
+        };
+
+        let accent_phrase_next = match (self.g1, self.g2, self.g3, self.g5) {
+            (Some(g1), Some(g2), Some(g3), g5) => Some(AccentPhrasePrevNext {
+                mora_count: g1,
+                accent_position: g2,
+                is_interrogative: g3,
+                // E5/G5's logics are inverted
+                is_pause_insertion: g5.map(|g5| !g5),
+            }),
+            (None, None, None, None) => None,
+            (g1, g2, g3, _) => {
+                return Err(missing(&[
+                    ("G1", g1.is_some()),
+                    ("G2", g2.is_some()),
+                    ("G3", g3.is_some()),
+                ]));
+            }
+        };
+
+        let breath_group =
+            |(count, mora): (Option<u8>, Option<u8>), names: [&'static str; 2]| match (count, mora)
+            {
+                (Some(accent_phrase_count), Some(mora_count)) => Ok(Some(BreathGroupPrevNext {
+                    accent_phrase_count,
+                    mora_count,
+                })),
+                (None, None) => Ok(None),
+                (count, _) => Err(BuildError::Missing(if count.is_none() {
+                    names[0]
+                } else {
+                    names[1]
+                })),
+            };
+
+        let breath_group_curr = match (
+            self.i1, self.i2, self.i3, self.i4, self.i5, self.i6, self.i7, self.i8,
+        ) {
+            (Some(i1), Some(i2), Some(i3), Some(i4), Some(i5), Some(i6), Some(i7), Some(i8)) => {
+                Some(BreathGroupCurrent {
+                    accent_phrase_count: i1,
+                    mora_count: i2,
+                    breath_group_position_forward: i3,
+                    breath_group_position_backward: i4,
+                    accent_phrase_position_forward: i5,
+                    accent_phrase_position_backward: i6,
+                    mora_position_forward: i7,
+                    mora_position_backward: i8,
+                })
+            }
+            (None, None, None, None, None, None, None, None) => None,
+            (i1, i2, i3, i4, i5, i6, i7, i8) => {
+                return Err(missing(&[
+                    ("I1", i1.is_some()),
+                    ("I2", i2.is_some()),
+                    ("I3", i3.is_some()),
+                    ("I4", i4.is_some()),
+                    ("I5", i5.is_some()),
+                    ("I6", i6.is_some()),
+                    ("I7", i7.is_some()),
+                    ("I8", i8.is_some()),
+                ]));
+            }
+        };
+
+        let utterance = match (self.k1, self.k2, self.k3) {
+            (Some(k1), Some(k2), Some(k3)) => Utterance {
+                breath_group_count: k1,
+                accent_phrase_count: k2,
+                mora_count: k3,
+            },
+            (k1, k2, k3) => {
+                return Err(missing(&[
+                    ("K1", k1.is_some()),
+                    ("K2", k2.is_some()),
+                    ("K3", k3.is_some()),
+                ]));
+            }
+        };
+
+        Ok(Label {
+            phoneme,
+            mora,
+            word_prev: word(self.b1, self.b2, self.b3),
+            word_curr: word(self.c1, self.c2, self.c3),
+            word_next: word(self.d1, self.d2, self.d3),
+            accent_phrase_prev,
+            accent_phrase_curr,
+            accent_phrase_next,
+            breath_group_prev: breath_group((self.h1, self.h2), ["H1", "H2"])?,
+            breath_group_curr,
+            breath_group_next: breath_group((self.j1, self.j2), ["J1", "J2"])?,
+            utterance,
+        })
+    }
+}
+
+/// The first field not set.
+fn missing(fields: &[(&'static str, bool)]) -> BuildError {
+    let (name, _) = fields
+        .iter()
+        .find(|(_, is_set)| !is_set)
+        .expect("Some field is missing");
+    BuildError::Missing(name)
+}
+
+fn validate(label: &Label) -> Result<(), BuildError> {
+    let check = |rule, left: i16, right: i16| {
+        if left == right {
+            Ok(())
+        } else {
+            Err(BuildError::Inconsistent { rule, left, right })
+        }
+    };
+    let sum = |forward: u8, backward: u8| i16::from(forward) + i16::from(backward) - 1;
+
+    if let (Some(mora), Some(ap)) = (&label.mora, &label.accent_phrase_curr) {
+        check(
+            "A1 = A2 - F2",
+            mora.relative_accent_position.into(),
+            i16::from(mora.position_forward) - i16::from(ap.accent_position),
+        )?;
+        check(
+            "A2 + A3 - 1 = F1",
+            sum(mora.position_forward, mora.position_backward),
+            ap.mora_count.into(),
+        )?;
+    }
+
+    if let (Some(ap), Some(bg)) = (&label.accent_phrase_curr, &label.breath_group_curr) {
+        check(
+            "F5 + F6 - 1 = I1",
+            sum(
+                ap.accent_phrase_position_forward,
+                ap.accent_phrase_position_backward,
+            ),
+            bg.accent_phrase_count.into(),
+        )?;
+        check(
+            "F7 + F8 - 1 = I2",
+            sum(ap.mora_position_forward, ap.mora_position_backward),
+            bg.mora_count.into(),
+        )?;
+    }
+
+    if let Some(bg) = &label.breath_group_curr {
+        let utterance = &label.utterance;
+        check(
+            "I3 + I4 - 1 = K1",
+            sum(
+                bg.breath_group_position_forward,
+                bg.breath_group_position_backward,
+            ),
+            utterance.breath_group_count.into(),
+        )?;
+        check(
+            "I5 + I6 - 1 = K2",
+            sum(
+                bg.accent_phrase_position_forward,
+                bg.accent_phrase_position_backward,
+            ),
+            utterance.accent_phrase_count.into(),
+        )?;
+        check(
+            "I7 + I8 - 1 = K3",
+            sum(bg.mora_position_forward, bg.mora_position_backward),
+            utterance.mora_count.into(),
+        )?;
+    }
+
+    Ok(())
+}
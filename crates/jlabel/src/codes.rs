@@ -0,0 +1,174 @@
+//! Code tables of part of speech and conjugation in [`Word`]
+//!
+//! The codes and names follow the question set for Japanese full-context labels
+//! used with Open JTalk.
+//!
+//! ```rust
+//! use jlabel::{Word, codes::{ConjugationForm, ConjugationType, PartOfSpeech}};
+//!
+//! let word = Word {
+//!     pos: Some(20),
+//!     ctype: Some(1),
+//!     cform: Some(1),
+//! };
+//! assert_eq!(word.part_of_speech(), Some(PartOfSpeech::MainVerb));
+//! assert_eq!(word.conjugation_type(), Some(ConjugationType::Godan));
+//! assert_eq!(word.conjugation_form(), Some(ConjugationForm::Continuative));
+//!
+//! assert_eq!(PartOfSpeech::MainVerb.japanese(), "本動詞");
+//! assert_eq!(PartOfSpeech::from_name("case particle"), Some(PartOfSpeech::CaseParticle));
+//! assert_eq!(u8::from(PartOfSpeech::CaseParticle), 13);
+//! ```
+
+use core::fmt::{Display, Formatter, Result};
+
+use crate::Word;
+
+macro_rules! code_table {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($variant:ident = $code:literal $(| $alias:literal)*, $japanese:literal, $english:literal;)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                #[doc = concat!($japanese, " (", $english, ")")]
+                $variant,
+            )*
+        }
+
+        impl $name {
+            /// All the values in the order of the codes.
+            pub const ALL: &[Self] = &[$(Self::$variant),*];
+
+            /// Converts from the code, or returns `None` if the code is not defined.
+            pub fn from_code(code: u8) -> Option<Self> {
+                match code {
+                    $($code $(| $alias)* => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// The code written in full-context label.
+            pub fn code(self) -> u8 {
+                match self {
+                    $(Self::$variant => $code,)*
+                }
+            }
+
+            /// The Japanese name.
+            pub fn japanese(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $japanese,)*
+                }
+            }
+
+            /// The English name in lowercase.
+            pub fn english(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $english,)*
+                }
+            }
+
+            /// Converts from the Japanese or English name.
+            pub fn from_name(name: &str) -> Option<Self> {
+                Self::ALL
+                    .iter()
+                    .copied()
+                    .find(|value| value.japanese() == name || value.english() == name)
+            }
+        }
+
+        impl From<$name> for u8 {
+            fn from(value: $name) -> Self {
+                value.code()
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+                f.write_str(self.japanese())
+            }
+        }
+    };
+}
+
+code_table! {
+    /// Part of speech (`B1`, `C1`, `D1`).
+    ///
+    /// Code 21 is not used.
+    PartOfSpeech {
+        Adjective = 1, "形容詞", "adjective";
+        CommonNoun = 2, "普通名詞", "common noun";
+        SahenNoun = 3, "サ変名詞", "sahen noun";
+        Pronoun = 4, "代名詞", "pronoun";
+        Numeral = 5, "数詞", "numeral";
+        Adverb = 6, "副詞", "adverb";
+        Adnominal = 7, "連体詞", "adnominal";
+        Conjunction = 8, "接続詞", "conjunction";
+        Interjection = 9, "感動詞", "interjection";
+        AuxiliaryVerb = 10, "助動詞", "auxiliary verb";
+        AdverbialParticle = 11, "副助詞", "adverbial particle";
+        ConjunctiveParticle = 12, "接続助詞", "conjunctive particle";
+        CaseParticle = 13, "格助詞", "case particle";
+        SentenceEndingParticle = 14, "終助詞", "sentence-ending particle";
+        Suffix = 15, "接尾語", "suffix";
+        Prefix = 16, "接頭語", "prefix";
+        SubsidiaryVerb = 17, "補助動詞", "subsidiary verb";
+        ProperNoun = 18, "固有名詞", "proper noun";
+        AdjectivalNoun = 19, "形容名詞", "adjectival noun";
+        MainVerb = 20, "本動詞", "main verb";
+        NominalizingParticle = 22, "準体助詞", "nominalizing particle";
+        ParallelParticle = 23, "並列助詞", "parallel particle";
+        BindingParticle = 24, "係助詞", "binding particle";
+        Filler = 25, "フィラー", "filler";
+    }
+}
+
+code_table! {
+    /// Conjugation type (`B2`, `C2`, `D2`).
+    ///
+    /// Both 2 and 3 are [`ConjugationType::Ichidan`], and the code of it is 2.
+    ConjugationType {
+        Irregular = 0, "変則型", "irregular";
+        Godan = 1, "五段", "godan";
+        Ichidan = 2 | 3, "一段", "ichidan";
+        Sahen = 4, "サ変", "sahen";
+        Kahen = 5, "カ変", "kahen";
+        Special = 6, "特殊型", "special";
+        Undefined = 7, "未定義型", "undefined";
+    }
+}
+
+code_table! {
+    /// Conjugation form (`B3`, `C3`, `D3`).
+    ConjugationForm {
+        Irrealis = 0, "未然形", "irrealis";
+        Continuative = 1, "連用形", "continuative";
+        Terminal = 2, "終止形", "terminal";
+        Attributive = 3, "連体形", "attributive";
+        Hypothetical = 4, "仮定形", "hypothetical";
+        Imperative = 5, "命令形", "imperative";
+        Stem = 6, "語幹", "stem";
+    }
+}
+
+impl Word {
+    /// The part of speech, or `None` if it is undefined or unknown code.
+    pub fn part_of_speech(&self) -> Option<PartOfSpeech> {
+        self.pos.and_then(PartOfSpeech::from_code)
+    }
+
+    /// The conjugation type, or `None` if it is undefined or unknown code.
+    pub fn conjugation_type(&self) -> Option<ConjugationType> {
+        self.ctype.and_then(ConjugationType::from_code)
+    }
+
+    /// The conjugation form, or `None` if it is undefined or unknown code.
+    pub fn conjugation_form(&self) -> Option<ConjugationForm> {
+        self.cform.and_then(ConjugationForm::from_code)
+    }
+}
@@ -0,0 +1,194 @@
+//! Field-level differences between two label sequences
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::{Label, diff::diff};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let old: Vec<Label> = [
+//!     "xx^xx-sil+k=o/A:xx+xx+xx/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:xx_xx#xx_xx@xx_xx|xx_xx/G:2_1%0_xx_xx/H:xx_xx/I:xx-xx@xx+xx&xx-xx|xx+xx/J:1_2/K:1+1-2",
+//!     "xx^sil-k+o=sil/A:0+1+2/B:xx-xx_xx/C:02_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:2_1#0_xx@1_1|1_2/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-2@1+1&1-1|1+2/J:xx_xx/K:1+1-2",
+//! ]
+//! .iter()
+//! .map(|s| s.parse())
+//! .collect::<Result<_, _>>()?;
+//! let mut new = old.clone();
+//! new[1].mora.as_mut().unwrap().relative_accent_position = -1;
+//!
+//! let diff = diff(&old, &new);
+//! assert_eq!(diff.to_string(), " sil\n~k\n    A1: 0 -> -1\n");
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::{Display, Formatter, Result};
+
+use crate::{
+    Label,
+    field::{FieldId, split},
+};
+
+/// A field whose value differs between the aligned labels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    /// The field, e.g. `A1` or `P3`.
+    pub field: FieldId,
+    /// The value in the old label, or `None` if it is `xx`.
+    pub old: Option<String>,
+    /// The value in the new label, or `None` if it is `xx`.
+    pub new: Option<String>,
+}
+
+impl Display for FieldChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.field,
+            self.old.as_deref().unwrap_or("xx"),
+            self.new.as_deref().unwrap_or("xx")
+        )
+    }
+}
+
+/// An entry of [`LabelDiff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffEntry {
+    /// The labels with the same phoneme are aligned.
+    /// `changes` is empty if the labels are identical.
+    Aligned {
+        /// The index in the old sequence.
+        old: usize,
+        /// The index in the new sequence.
+        new: usize,
+        /// The fields which differ.
+        changes: Vec<FieldChange>,
+    },
+    /// The label exists only in the old sequence.
+    Deleted {
+        /// The index in the old sequence.
+        old: usize,
+    },
+    /// The label exists only in the new sequence.
+    Inserted {
+        /// The index in the new sequence.
+        new: usize,
+    },
+}
+
+/// The differences between two label sequences.
+///
+/// This is the result of [`diff`].
+///
+/// With [`Display`], it is rendered in the style of unified diff, one phoneme per line:
+/// ` ` for identical labels, `-` for deleted ones, `+` for inserted ones,
+/// and `~` for the aligned labels with field changes, followed by the changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelDiff<'a> {
+    old: &'a [Label],
+    new: &'a [Label],
+    entries: Vec<DiffEntry>,
+}
+
+impl LabelDiff<'_> {
+    /// The entries in the order of the sequences.
+    pub fn entries(&self) -> &[DiffEntry] {
+        &self.entries
+    }
+
+    /// Returns `true` if the two sequences are identical.
+    pub fn is_empty(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|entry| matches!(entry, DiffEntry::Aligned { changes, .. } if changes.is_empty()))
+    }
+}
+
+impl Display for LabelDiff<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for entry in &self.entries {
+            match entry {
+                DiffEntry::Aligned { old, changes, .. } => {
+                    let mark = if changes.is_empty() { ' ' } else { '~' };
+                    writeln!(f, "{mark}{}", phoneme(&self.old[*old]))?;
+                    for change in changes {
+                        writeln!(f, "    {change}")?;
+                    }
+                }
+                DiffEntry::Deleted { old } => writeln!(f, "-{}", phoneme(&self.old[*old]))?,
+                DiffEntry::Inserted { new } => writeln!(f, "+{}", phoneme(&self.new[*new]))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Aligns two label sequences by the current phonemes (P3),
+/// and reports the field-level differences of the aligned labels.
+///
+/// The alignment is the longest common subsequence of the phonemes,
+/// so insertions and deletions such as of `pau` are reported as [`DiffEntry::Inserted`] and [`DiffEntry::Deleted`].
+/// This takes time and memory proportional to the product of the lengths.
+pub fn diff<'a>(old: &'a [Label], new: &'a [Label]) -> LabelDiff<'a> {
+    let (n, m) = (old.len(), new.len());
+
+    // lcs[i][j] is the length of LCS of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i].phoneme.c == new[j].phoneme.c {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut entries = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[i].phoneme.c == new[j].phoneme.c {
+            entries.push(DiffEntry::Aligned {
+                old: i,
+                new: j,
+                changes: changes(&old[i], &new[j]),
+            });
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            entries.push(DiffEntry::Inserted { new: j });
+            j += 1;
+        } else {
+            entries.push(DiffEntry::Deleted { old: i });
+            i += 1;
+        }
+    }
+
+    LabelDiff { old, new, entries }
+}
+
+fn phoneme(label: &Label) -> &str {
+    label.phoneme.c.as_deref().unwrap_or("xx")
+}
+
+fn changes(old: &Label, new: &Label) -> Vec<FieldChange> {
+    if old == new {
+        return Vec::new();
+    }
+    let (old, new) = (old.to_string(), new.to_string());
+    split(&old)
+        .zip(split(&new))
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((field, old), (_, new))| FieldChange {
+            field,
+            old: (old != "xx").then(|| old.to_string()),
+            new: (new != "xx").then(|| new.to_string()),
+        })
+        .collect()
+}
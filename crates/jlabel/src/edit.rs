@@ -0,0 +1,482 @@
+//! Editing operations on label sequence
+//!
+//! [`LabelEditor`] holds the label sequence as breath groups, accent phrases and moras,
+//! and regenerates all the fields of moras, accent phrases, breath groups and utterance
+//! (`A`, `E` to `K`) and surrounding phonemes (`P1`, `P2`, `P4`, `P5`) on [`LabelEditor::to_labels`].
+//! The fields of words (`B`, `C`, `D`) are kept as they are.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::{edit::LabelEditor, kana::{from_kana, to_kana}};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let labels = from_kana("コンニチワ'/ゲンキ'デスカ？")?;
+//!
+//! let mut editor = LabelEditor::new(&labels)?;
+//! editor.set_accent_position(1, 1)?;
+//! editor.insert_pause(0)?;
+//! editor.split_phrase(1, 3)?;
+//! assert_eq!(to_kana(&editor.to_labels()), "コンニチワ'、ゲ'ンキ/デスカ'？");
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    AccentPhraseCurrent, AccentPhrasePrevNext, BreathGroupCurrent, BreathGroupPrevNext, Label,
+    Mora, Phoneme, Utterance, segment,
+};
+
+/// Errors from [`LabelEditor`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EditError {
+    /// The label sequence is not in the form of `sil`, breath groups separated by a `pau`, and `sil`.
+    #[error("Unexpected label at {0}")]
+    Malformed(usize),
+    /// There is no accent phrase of the index.
+    #[error("Accent phrase {0} out of range")]
+    PhraseOutOfRange(usize),
+    /// The accent phrase cannot be split at the mora.
+    #[error("Mora {0} out of range")]
+    MoraOutOfRange(usize),
+    /// The accent position exceeds the number of moras.
+    #[error("Accent position {0} out of range")]
+    AccentOutOfRange(u8),
+    /// There is already a pause after the accent phrase.
+    #[error("Pause already exists after accent phrase {0}")]
+    PauseExists(usize),
+    /// There is no pause after the accent phrase.
+    #[error("No pause after accent phrase {0}")]
+    NoPause(usize),
+}
+
+/// Label sequence which keeps the consistency on editing.
+///
+/// Accent phrases are specified by the index in the utterance, starting from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelEditor {
+    /// The `sil` at the beginning
+    pub(crate) head: Label,
+    pub(crate) breath_groups: Vec<BreathGroup>,
+    /// The `sil` at the end
+    pub(crate) tail: Label,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BreathGroup {
+    /// The `pau` before the breath group, which is `None` for the first breath group.
+    pub pause: Option<Label>,
+    pub phrases: Vec<Phrase>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Phrase {
+    /// The labels of each mora.
+    pub moras: Vec<Vec<Label>>,
+    /// The accent position as in full-context label, where type 0 is the number of moras.
+    pub accent_position: u8,
+    pub is_interrogative: bool,
+}
+
+impl Phrase {
+    fn mora_count(&self) -> u8 {
+        self.moras.len() as u8
+    }
+
+    fn prev_next(&self, is_pause_insertion: Option<bool>) -> AccentPhrasePrevNext {
+        AccentPhrasePrevNext {
+            mora_count: self.mora_count(),
+            accent_position: self.accent_position,
+            is_interrogative: self.is_interrogative,
+            is_pause_insertion,
+        }
+    }
+}
+
+/// A label with only the current phoneme, to be filled by [`LabelEditor::to_labels`].
+pub(crate) fn template(phoneme: &str) -> Label {
+    Label {
+        phoneme: Phoneme {
+            p2: None,
+            p1: None,
+            c: Some(phoneme.to_string()),
+            n1: None,
+            n2: None,
+        },
+        mora: None,
+        word_prev: None,
+        word_curr: None,
+        word_next: None,
+        accent_phrase_prev: None,
+        accent_phrase_curr: None,
+        accent_phrase_next: None,
+        breath_group_prev: None,
+        breath_group_curr: None,
+        breath_group_next: None,
+        utterance: Utterance {
+            breath_group_count: 0,
+            accent_phrase_count: 0,
+            mora_count: 0,
+        },
+    }
+}
+
+impl LabelEditor {
+    /// Groups the label sequence into breath groups, accent phrases and moras.
+    ///
+    /// The sequence must start and end with labels without mora (`sil`),
+    /// and breath groups must be separated by exactly one label without mora (`pau`).
+    /// Accent phrases and moras are grouped in the same way as [`crate::pitch::pitch`].
+    pub fn new(labels: &[Label]) -> Result<Self, EditError> {
+        let (head, tail) = match labels {
+            [head, .., tail] if head.mora.is_none() && tail.mora.is_none() => (head, tail),
+            [head, ..] if head.mora.is_some() => return Err(EditError::Malformed(0)),
+            _ => return Err(EditError::Malformed(labels.len().saturating_sub(1))),
+        };
+
+        let mut breath_groups: Vec<BreathGroup> = Vec::new();
+        // The index next to the last accent phrase
+        let mut prev_end = 1;
+        for phrase in segment::phrases(labels) {
+            let (start, gap) = (prev_end, phrase.labels.start - prev_end);
+            prev_end = phrase.labels.end;
+
+            let accent_phrase = labels[phrase.labels.start].accent_phrase_curr.as_ref();
+            let mora_count = phrase.moras.len() as u8;
+            let phrase = Phrase {
+                moras: phrase
+                    .moras
+                    .into_iter()
+                    .map(|mora| labels[mora].to_vec())
+                    .collect(),
+                accent_position: accent_phrase
+                    .map(|ap| ap.accent_position)
+                    .filter(|accent_position| *accent_position != 0)
+                    .map_or(mora_count, |accent_position| {
+                        accent_position.min(mora_count)
+                    }),
+                is_interrogative: accent_phrase.is_some_and(|ap| ap.is_interrogative),
+            };
+
+            match (gap, breath_groups.last_mut()) {
+                (0, Some(breath_group)) => breath_group.phrases.push(phrase),
+                (0, None) => breath_groups.push(BreathGroup {
+                    pause: None,
+                    phrases: vec![phrase],
+                }),
+                (1, Some(_)) => breath_groups.push(BreathGroup {
+                    pause: Some(labels[start].clone()),
+                    phrases: vec![phrase],
+                }),
+                (_, Some(_)) => return Err(EditError::Malformed(start + 1)),
+                (_, None) => return Err(EditError::Malformed(start)),
+            }
+        }
+
+        if breath_groups.is_empty() || prev_end != labels.len() - 1 {
+            return Err(EditError::Malformed(prev_end));
+        }
+
+        Ok(Self {
+            head: head.clone(),
+            breath_groups,
+            tail: tail.clone(),
+        })
+    }
+
+    /// The number of accent phrases.
+    pub fn phrase_count(&self) -> usize {
+        self.breath_groups
+            .iter()
+            .map(|breath_group| breath_group.phrases.len())
+            .sum()
+    }
+
+    /// The number of moras in the accent phrase.
+    pub fn mora_count(&self, phrase: usize) -> Result<usize, EditError> {
+        Ok(self.phrase(phrase)?.moras.len())
+    }
+
+    /// Sets the accent position of the accent phrase.
+    ///
+    /// `0` means type 0 (heiban), which is written as the number of moras in full-context label.
+    pub fn set_accent_position(
+        &mut self,
+        phrase: usize,
+        accent_position: u8,
+    ) -> Result<(), EditError> {
+        let phrase = self.phrase_mut(phrase)?;
+        phrase.accent_position = match accent_position {
+            0 => phrase.mora_count(),
+            a if a <= phrase.mora_count() => a,
+            a => return Err(EditError::AccentOutOfRange(a)),
+        };
+        Ok(())
+    }
+
+    /// Sets whether the accent phrase is interrogative.
+    pub fn set_interrogative(
+        &mut self,
+        phrase: usize,
+        is_interrogative: bool,
+    ) -> Result<(), EditError> {
+        self.phrase_mut(phrase)?.is_interrogative = is_interrogative;
+        Ok(())
+    }
+
+    /// Splits the accent phrase into two, where the latter starts from the `mora`-th mora (0-based).
+    ///
+    /// The accent nucleus goes to the part it is in, and the other part becomes type 0 (heiban).
+    /// The latter part is interrogative if the original accent phrase is.
+    pub fn split_phrase(&mut self, phrase: usize, mora: usize) -> Result<(), EditError> {
+        let (b, p) = self.locate(phrase)?;
+        let phrases = &mut self.breath_groups[b].phrases;
+        let former = &mut phrases[p];
+        if mora == 0 || mora >= former.moras.len() {
+            return Err(EditError::MoraOutOfRange(mora));
+        }
+
+        let moras = former.moras.split_off(mora);
+        let (split, total) = (mora as u8, (mora + moras.len()) as u8);
+        let latter = Phrase {
+            accent_position: match former.accent_position {
+                a if a > split && a < total => a - split,
+                _ => total - split,
+            },
+            moras,
+            is_interrogative: former.is_interrogative,
+        };
+        if former.accent_position > split {
+            former.accent_position = split;
+        }
+        former.is_interrogative = false;
+
+        phrases.insert(p + 1, latter);
+        Ok(())
+    }
+
+    /// Merges the accent phrase and the next one.
+    ///
+    /// The pause between them is removed if any.
+    /// The accent nucleus of the former is kept, and otherwise that of the latter.
+    /// The merged accent phrase is interrogative if the latter is.
+    pub fn merge_phrases(&mut self, phrase: usize) -> Result<(), EditError> {
+        let (b, p) = self.locate(phrase)?;
+        self.locate(phrase + 1)?;
+        if p + 1 == self.breath_groups[b].phrases.len() {
+            self.remove_pause(phrase)?;
+        }
+
+        let phrases = &mut self.breath_groups[b].phrases;
+        let latter = phrases.remove(p + 1);
+        let former = &mut phrases[p];
+        if former.accent_position == former.mora_count() {
+            // If the former is type 0 (heiban), and so is the latter, the merged one is also type 0
+            former.accent_position += latter.accent_position;
+        }
+        former.moras.extend(latter.moras);
+        former.is_interrogative = latter.is_interrogative;
+        Ok(())
+    }
+
+    /// Inserts a pause after the accent phrase, which splits the breath group.
+    pub fn insert_pause(&mut self, phrase: usize) -> Result<(), EditError> {
+        let (b, p) = self.locate(phrase)?;
+        self.locate(phrase + 1)?;
+        let breath_group = &mut self.breath_groups[b];
+        if p + 1 == breath_group.phrases.len() {
+            return Err(EditError::PauseExists(phrase));
+        }
+
+        let phrases = breath_group.phrases.split_off(p + 1);
+        self.breath_groups.insert(
+            b + 1,
+            BreathGroup {
+                pause: Some(template("pau")),
+                phrases,
+            },
+        );
+        Ok(())
+    }
+
+    /// Removes the pause after the accent phrase, which joins the breath groups.
+    pub fn remove_pause(&mut self, phrase: usize) -> Result<(), EditError> {
+        let (b, p) = self.locate(phrase)?;
+        if p + 1 != self.breath_groups[b].phrases.len() || b + 1 == self.breath_groups.len() {
+            return Err(EditError::NoPause(phrase));
+        }
+
+        let next = self.breath_groups.remove(b + 1);
+        self.breath_groups[b].phrases.extend(next.phrases);
+        Ok(())
+    }
+
+    /// Generates the label sequence.
+    pub fn to_labels(&self) -> Vec<Label> {
+        let breath_groups = &self.breath_groups;
+        let phrases: Vec<&Phrase> = breath_groups
+            .iter()
+            .flat_map(|breath_group| &breath_group.phrases)
+            .collect();
+        let breath_group_info = |breath_group: &BreathGroup| BreathGroupPrevNext {
+            accent_phrase_count: breath_group.phrases.len() as u8,
+            mora_count: breath_group.phrases.iter().map(Phrase::mora_count).sum(),
+        };
+        let mora_count = breath_groups
+            .iter()
+            .map(|breath_group| breath_group_info(breath_group).mora_count)
+            .sum();
+        let utterance = Utterance {
+            breath_group_count: breath_groups.len() as u8,
+            accent_phrase_count: phrases.len() as u8,
+            mora_count,
+        };
+        // Clears the fields to be generated
+        let base = |label: &Label| Label {
+            mora: None,
+            accent_phrase_prev: None,
+            accent_phrase_curr: None,
+            accent_phrase_next: None,
+            breath_group_prev: None,
+            breath_group_curr: None,
+            breath_group_next: None,
+            utterance: utterance.clone(),
+            ..label.clone()
+        };
+
+        let mut labels = vec![Label {
+            accent_phrase_next: Some(phrases[0].prev_next(None)),
+            breath_group_next: Some(breath_group_info(&breath_groups[0])),
+            ..base(&self.head)
+        }];
+
+        // The index of the first accent phrase and mora of the breath group in the utterance
+        let (mut phrase_offset, mut mora_offset) = (0, 0);
+        for (b, breath_group) in breath_groups.iter().enumerate() {
+            let info = breath_group_info(breath_group);
+            let breath_group_curr = BreathGroupCurrent {
+                accent_phrase_count: info.accent_phrase_count,
+                mora_count: info.mora_count,
+                breath_group_position_forward: b as u8 + 1,
+                breath_group_position_backward: (breath_groups.len() - b) as u8,
+                accent_phrase_position_forward: phrase_offset as u8 + 1,
+                accent_phrase_position_backward: (phrases.len() - phrase_offset) as u8,
+                mora_position_forward: mora_offset + 1,
+                mora_position_backward: mora_count - mora_offset,
+            };
+            let breath_group_prev = b
+                .checked_sub(1)
+                .map(|b| breath_group_info(&breath_groups[b]));
+            let breath_group_next = breath_groups.get(b + 1).map(breath_group_info);
+
+            if let Some(pause) = &breath_group.pause {
+                labels.push(Label {
+                    accent_phrase_prev: Some(phrases[phrase_offset - 1].prev_next(None)),
+                    accent_phrase_next: Some(phrases[phrase_offset].prev_next(None)),
+                    breath_group_prev: breath_group_prev.clone(),
+                    breath_group_next: Some(info.clone()),
+                    ..base(pause)
+                });
+            }
+
+            // The index of the first mora of the accent phrase in the breath group
+            let mut group_mora_offset = 0;
+            for (a, phrase) in breath_group.phrases.iter().enumerate() {
+                let g = phrase_offset + a;
+                let accent_phrase_prev = g
+                    .checked_sub(1)
+                    .map(|prev| phrases[prev].prev_next(Some(a == 0)));
+                let accent_phrase_next = phrases
+                    .get(g + 1)
+                    .map(|next| next.prev_next(Some(a + 1 == breath_group.phrases.len())));
+                let accent_phrase_curr = AccentPhraseCurrent {
+                    mora_count: phrase.mora_count(),
+                    accent_position: phrase.accent_position,
+                    is_interrogative: phrase.is_interrogative,
+                    accent_phrase_position_forward: a as u8 + 1,
+                    accent_phrase_position_backward: (breath_group.phrases.len() - a) as u8,
+                    mora_position_forward: group_mora_offset + 1,
+                    mora_position_backward: info.mora_count - group_mora_offset,
+                };
+
+                for (m, mora_labels) in phrase.moras.iter().enumerate() {
+                    let position = m as i16 + 1;
+                    let mora = Mora {
+                        relative_accent_position: (position - i16::from(phrase.accent_position))
+                            .clamp(i8::MIN.into(), i8::MAX.into())
+                            as i8,
+                        position_forward: position as u8,
+                        position_backward: phrase.mora_count() - m as u8,
+                    };
+                    labels.extend(mora_labels.iter().map(|label| Label {
+                        mora: Some(mora.clone()),
+                        accent_phrase_prev: accent_phrase_prev.clone(),
+                        accent_phrase_curr: Some(accent_phrase_curr.clone()),
+                        accent_phrase_next: accent_phrase_next.clone(),
+                        breath_group_prev: breath_group_prev.clone(),
+                        breath_group_curr: Some(breath_group_curr.clone()),
+                        breath_group_next: breath_group_next.clone(),
+                        ..base(label)
+                    }));
+                }
+                group_mora_offset += phrase.mora_count();
+            }
+
+            phrase_offset += breath_group.phrases.len();
+            mora_offset += info.mora_count;
+        }
+
+        labels.push(Label {
+            accent_phrase_prev: Some(phrases[phrases.len() - 1].prev_next(None)),
+            breath_group_prev: Some(breath_group_info(&breath_groups[breath_groups.len() - 1])),
+            ..base(&self.tail)
+        });
+
+        // Fill the surrounding phonemes
+        let phonemes: Vec<Option<String>> =
+            labels.iter().map(|label| label.phoneme.c.clone()).collect();
+        for (index, label) in labels.iter_mut().enumerate() {
+            let at = |offset: isize| {
+                index
+                    .checked_add_signed(offset)
+                    .and_then(|index| phonemes.get(index))
+                    .cloned()
+                    .flatten()
+            };
+            label.phoneme.p2 = at(-2);
+            label.phoneme.p1 = at(-1);
+            label.phoneme.n1 = at(1);
+            label.phoneme.n2 = at(2);
+        }
+
+        labels
+    }
+
+    /// The index of the breath group and the accent phrase in it.
+    fn locate(&self, phrase: usize) -> Result<(usize, usize), EditError> {
+        let mut index = phrase;
+        for (b, breath_group) in self.breath_groups.iter().enumerate() {
+            if index < breath_group.phrases.len() {
+                return Ok((b, index));
+            }
+            index -= breath_group.phrases.len();
+        }
+        Err(EditError::PhraseOutOfRange(phrase))
+    }
+
+    fn phrase(&self, phrase: usize) -> Result<&Phrase, EditError> {
+        let (b, p) = self.locate(phrase)?;
+        Ok(&self.breath_groups[b].phrases[p])
+    }
+
+    fn phrase_mut(&mut self, phrase: usize) -> Result<&mut Phrase, EditError> {
+        let (b, p) = self.locate(phrase)?;
+        Ok(&mut self.breath_groups[b].phrases[p])
+    }
+}
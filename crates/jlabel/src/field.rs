@@ -0,0 +1,456 @@
+//! Access to the fields of [`Label`] by their codes
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::{Label, field::{FieldId, FieldValue}};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let mut label: Label = "xx^sil-k+o=N/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5".parse()?;
+//!
+//! let f7: FieldId = "F7".parse()?;
+//! assert_eq!(label.get_field(f7), Some(FieldValue::Unsigned(1)));
+//! assert_eq!(label.get_field(FieldId::P1), None);
+//!
+//! label.set_field(FieldId::A1, Some(FieldValue::Signed(0)))?;
+//! assert_eq!(label.mora.as_ref().unwrap().relative_accent_position, 0);
+//!
+//! assert_eq!(label.fields().count(), 50);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use alloc::string::{String, ToString};
+use core::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::Label;
+
+/// Errors from [`Label::set_field`] and parsing [`FieldId`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum FieldError {
+    /// The code is not a field of full-context label.
+    #[error("Unknown field {0:?}")]
+    Unknown(String),
+    /// The value is not of the type of the field.
+    #[error("Type mismatch: {0} expects {1}")]
+    TypeMismatch(FieldId, &'static str),
+    /// The field is always undefined (`E4`, `F4`, `G4`).
+    #[error("{0} must be undefined")]
+    NotUndefined(FieldId),
+    /// The field cannot be undefined (`K`).
+    #[error("{0} cannot be undefined")]
+    Required(FieldId),
+}
+
+macro_rules! field_ids {
+    ($($id:ident $delimiter:literal,)*) => {
+        /// The code of a field of full-context label, e.g. `P3` or `F7`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum FieldId {
+            $(
+                #[doc = stringify!($id)]
+                $id,
+            )*
+        }
+
+        impl FieldId {
+            /// All the fields in the order of full-context label.
+            pub const ALL: [Self; 50] = [$(Self::$id),*];
+
+            /// The code of the field.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Self::$id => stringify!($id),)*
+                }
+            }
+
+            /// The delimiter following the field in full-context label.
+            pub(crate) fn delimiter(self) -> &'static str {
+                match self {
+                    $(Self::$id => $delimiter,)*
+                }
+            }
+        }
+    };
+}
+
+field_ids! {
+    P1 "^", P2 "-", P3 "+", P4 "=", P5 "/A:",
+    A1 "+", A2 "+", A3 "/B:",
+    B1 "-", B2 "_", B3 "/C:",
+    C1 "_", C2 "+", C3 "/D:",
+    D1 "+", D2 "_", D3 "/E:",
+    E1 "_", E2 "!", E3 "_", E4 "-", E5 "/F:",
+    F1 "_", F2 "#", F3 "_", F4 "@", F5 "_", F6 "|", F7 "_", F8 "/G:",
+    G1 "_", G2 "%", G3 "_", G4 "_", G5 "/H:",
+    H1 "_", H2 "/I:",
+    I1 "-", I2 "@", I3 "+", I4 "&", I5 "-", I6 "|", I7 "+", I8 "/J:",
+    J1 "_", J2 "/K:",
+    K1 "+", K2 "-", K3 "",
+}
+
+impl Display for FieldId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FieldId {
+    type Err = FieldError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|id| id.name() == s)
+            .ok_or_else(|| FieldError::Unknown(s.to_string()))
+    }
+}
+
+/// The value of a field.
+///
+/// Values are the same as they appear in the full-context label.
+/// In particular, `E5` and `G5` are the inverse of [`crate::AccentPhrasePrevNext::is_pause_insertion`],
+/// i.e. `Boolean(true)` (`1`) means no pause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    /// Phoneme (`P1` to `P5`)
+    Phoneme(String),
+    /// Signed integer (`A1`)
+    Signed(i8),
+    /// Unsigned integer
+    Unsigned(u8),
+    /// Boolean (`E3`, `E5`, `F3`, `G3`, `G5`)
+    Boolean(bool),
+}
+
+impl Display for FieldValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Phoneme(phoneme) => f.write_str(phoneme),
+            Self::Signed(value) => write!(f, "{value}"),
+            Self::Unsigned(value) => write!(f, "{value}"),
+            Self::Boolean(value) => write!(f, "{}", u8::from(*value)),
+        }
+    }
+}
+
+impl Label {
+    /// Gets the value of the field, or `None` if it is undefined (`xx`).
+    pub fn get_field(&self, field: FieldId) -> Option<FieldValue> {
+        use FieldId::*;
+        use FieldValue::*;
+
+        let phoneme = &self.phoneme;
+        let mora = self.mora.as_ref();
+        let word = |word: &Option<crate::Word>| word.clone().unwrap_or_default();
+        let (b, c, d) = (
+            word(&self.word_prev),
+            word(&self.word_curr),
+            word(&self.word_next),
+        );
+        let e = self.accent_phrase_prev.as_ref();
+        let f = self.accent_phrase_curr.as_ref();
+        let g = self.accent_phrase_next.as_ref();
+        let h = self.breath_group_prev.as_ref();
+        let i = self.breath_group_curr.as_ref();
+        let j = self.breath_group_next.as_ref();
+        let k = &self.utterance;
+
+        match field {
+            P1 => phoneme.p2.clone().map(Phoneme),
+            P2 => phoneme.p1.clone().map(Phoneme),
+            P3 => phoneme.c.clone().map(Phoneme),
+            P4 => phoneme.n1.clone().map(Phoneme),
+            P5 => phoneme.n2.clone().map(Phoneme),
+            A1 => mora.map(|a| Signed(a.relative_accent_position)),
+            A2 => mora.map(|a| Unsigned(a.position_forward)),
+            A3 => mora.map(|a| Unsigned(a.position_backward)),
+            B1 => b.pos.map(Unsigned),
+            B2 => b.ctype.map(Unsigned),
+            B3 => b.cform.map(Unsigned),
+            C1 => c.pos.map(Unsigned),
+            C2 => c.ctype.map(Unsigned),
+            C3 => c.cform.map(Unsigned),
+            D1 => d.pos.map(Unsigned),
+            D2 => d.ctype.map(Unsigned),
+            D3 => d.cform.map(Unsigned),
+            E1 => e.map(|e| Unsigned(e.mora_count)),
+            E2 => e.map(|e| Unsigned(e.accent_position)),
+            E3 => e.map(|e| Boolean(e.is_interrogative)),
+            // E5/G5's logics are inverted
+            E5 => e.and_then(|e| e.is_pause_insertion).map(|e5| Boolean(!e5)),
+            F1 => f.map(|f| Unsigned(f.mora_count)),
+            F2 => f.map(|f| Unsigned(f.accent_position)),
+            F3 => f.map(|f| Boolean(f.is_interrogative)),
+            F5 => f.map(|f| Unsigned(f.accent_phrase_position_forward)),
+            F6 => f.map(|f| Unsigned(f.accent_phrase_position_backward)),
+            F7 => f.map(|f| Unsigned(f.mora_position_forward)),
+            F8 => f.map(|f| Unsigned(f.mora_position_backward)),
+            G1 => g.map(|g| Unsigned(g.mora_count)),
+            G2 => g.map(|g| Unsigned(g.accent_position)),
+            G3 => g.map(|g| Boolean(g.is_interrogative)),
+            G5 => g.and_then(|g| g.is_pause_insertion).map(|g5| Boolean(!g5)),
+            H1 => h.map(|h| Unsigned(h.accent_phrase_count)),
+            H2 => h.map(|h| Unsigned(h.mora_count)),
+            I1 => i.map(|i| Unsigned(i.accent_phrase_count)),
+            I2 => i.map(|i| Unsigned(i.mora_count)),
+            I3 => i.map(|i| Unsigned(i.breath_group_position_forward)),
+            I4 => i.map(|i| Unsigned(i.breath_group_position_backward)),
+            I5 => i.map(|i| Unsigned(i.accent_phrase_position_forward)),
+            I6 => i.map(|i| Unsigned(i.accent_phrase_position_backward)),
+            I7 => i.map(|i| Unsigned(i.mora_position_forward)),
+            I8 => i.map(|i| Unsigned(i.mora_position_backward)),
+            J1 => j.map(|j| Unsigned(j.accent_phrase_count)),
+            J2 => j.map(|j| Unsigned(j.mora_count)),
+            K1 => Some(Unsigned(k.breath_group_count)),
+            K2 => Some(Unsigned(k.accent_phrase_count)),
+            K3 => Some(Unsigned(k.mora_count)),
+            E4 | F4 | G4 => None,
+        }
+    }
+
+    /// Sets the value of the field, or makes it undefined (`xx`) with `None`.
+    ///
+    /// As in the parser, a block (e.g. `A`) is undefined as a whole:
+    /// making a field undefined also makes the other fields in the block undefined,
+    /// and setting a field in an undefined block sets the other fields to `0` (or `false`).
+    /// The exceptions are the fields of words (`B`, `C`, `D`), `E5` and `G5`,
+    /// which can be undefined individually.
+    pub fn set_field(
+        &mut self,
+        field: FieldId,
+        value: Option<FieldValue>,
+    ) -> Result<(), FieldError> {
+        use FieldId::*;
+
+        let value = Value { field, value };
+        match field {
+            P1 => self.phoneme.p2 = value.phoneme()?,
+            P2 => self.phoneme.p1 = value.phoneme()?,
+            P3 => self.phoneme.c = value.phoneme()?,
+            P4 => self.phoneme.n1 = value.phoneme()?,
+            P5 => self.phoneme.n2 = value.phoneme()?,
+            A1 => set(&mut self.mora, value.signed()?, |a| {
+                &mut a.relative_accent_position
+            }),
+            A2 => set(&mut self.mora, value.unsigned()?, |a| {
+                &mut a.position_forward
+            }),
+            A3 => set(&mut self.mora, value.unsigned()?, |a| {
+                &mut a.position_backward
+            }),
+            B1 => set_word(&mut self.word_prev, value.unsigned()?, |b| &mut b.pos),
+            B2 => set_word(&mut self.word_prev, value.unsigned()?, |b| &mut b.ctype),
+            B3 => set_word(&mut self.word_prev, value.unsigned()?, |b| &mut b.cform),
+            C1 => set_word(&mut self.word_curr, value.unsigned()?, |c| &mut c.pos),
+            C2 => set_word(&mut self.word_curr, value.unsigned()?, |c| &mut c.ctype),
+            C3 => set_word(&mut self.word_curr, value.unsigned()?, |c| &mut c.cform),
+            D1 => set_word(&mut self.word_next, value.unsigned()?, |d| &mut d.pos),
+            D2 => set_word(&mut self.word_next, value.unsigned()?, |d| &mut d.ctype),
+            D3 => set_word(&mut self.word_next, value.unsigned()?, |d| &mut d.cform),
+            E1 => set(&mut self.accent_phrase_prev, value.unsigned()?, |e| {
+                &mut e.mora_count
+            }),
+            E2 => set(&mut self.accent_phrase_prev, value.unsigned()?, |e| {
+                &mut e.accent_position
+            }),
+            E3 => set(&mut self.accent_phrase_prev, value.boolean()?, |e| {
+                &mut e.is_interrogative
+            }),
+            E5 => set_optional(&mut self.accent_phrase_prev, value.inverted()?, |e| {
+                &mut e.is_pause_insertion
+            }),
+            F1 => set(&mut self.accent_phrase_curr, value.unsigned()?, |f| {
+                &mut f.mora_count
+            }),
+            F2 => set(&mut self.accent_phrase_curr, value.unsigned()?, |f| {
+                &mut f.accent_position
+            }),
+            F3 => set(&mut self.accent_phrase_curr, value.boolean()?, |f| {
+                &mut f.is_interrogative
+            }),
+            F5 => set(&mut self.accent_phrase_curr, value.unsigned()?, |f| {
+                &mut f.accent_phrase_position_forward
+            }),
+            F6 => set(&mut self.accent_phrase_curr, value.unsigned()?, |f| {
+                &mut f.accent_phrase_position_backward
+            }),
+            F7 => set(&mut self.accent_phrase_curr, value.unsigned()?, |f| {
+                &mut f.mora_position_forward
+            }),
+            F8 => set(&mut self.accent_phrase_curr, value.unsigned()?, |f| {
+                &mut f.mora_position_backward
+            }),
+            G1 => set(&mut self.accent_phrase_next, value.unsigned()?, |g| {
+                &mut g.mora_count
+            }),
+            G2 => set(&mut self.accent_phrase_next, value.unsigned()?, |g| {
+                &mut g.accent_position
+            }),
+            G3 => set(&mut self.accent_phrase_next, value.boolean()?, |g| {
+                &mut g.is_interrogative
+            }),
+            G5 => set_optional(&mut self.accent_phrase_next, value.inverted()?, |g| {
+                &mut g.is_pause_insertion
+            }),
+            H1 => set(&mut self.breath_group_prev, value.unsigned()?, |h| {
+                &mut h.accent_phrase_count
+            }),
+            H2 => set(&mut self.breath_group_prev, value.unsigned()?, |h| {
+                &mut h.mora_count
+            }),
+            I1 => set(&mut self.breath_group_curr, value.unsigned()?, |i| {
+                &mut i.accent_phrase_count
+            }),
+            I2 => set(&mut self.breath_group_curr, value.unsigned()?, |i| {
+                &mut i.mora_count
+            }),
+            I3 => set(&mut self.breath_group_curr, value.unsigned()?, |i| {
+                &mut i.breath_group_position_forward
+            }),
+            I4 => set(&mut self.breath_group_curr, value.unsigned()?, |i| {
+                &mut i.breath_group_position_backward
+            }),
+            I5 => set(&mut self.breath_group_curr, value.unsigned()?, |i| {
+                &mut i.accent_phrase_position_forward
+            }),
+            I6 => set(&mut self.breath_group_curr, value.unsigned()?, |i| {
+                &mut i.accent_phrase_position_backward
+            }),
+            I7 => set(&mut self.breath_group_curr, value.unsigned()?, |i| {
+                &mut i.mora_position_forward
+            }),
+            I8 => set(&mut self.breath_group_curr, value.unsigned()?, |i| {
+                &mut i.mora_position_backward
+            }),
+            J1 => set(&mut self.breath_group_next, value.unsigned()?, |j| {
+                &mut j.accent_phrase_count
+            }),
+            J2 => set(&mut self.breath_group_next, value.unsigned()?, |j| {
+                &mut j.mora_count
+            }),
+            K1 => self.utterance.breath_group_count = value.required()?,
+            K2 => self.utterance.accent_phrase_count = value.required()?,
+            K3 => self.utterance.mora_count = value.required()?,
+            E4 | F4 | G4 => {
+                if value.value.is_some() {
+                    return Err(FieldError::NotUndefined(field));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Iterates over all the fields with their values in the order of full-context label.
+    pub fn fields(&self) -> impl Iterator<Item = (FieldId, Option<FieldValue>)> + '_ {
+        FieldId::ALL
+            .into_iter()
+            .map(|field| (field, self.get_field(field)))
+    }
+}
+
+/// The value to set, with the field for errors.
+struct Value {
+    field: FieldId,
+    value: Option<FieldValue>,
+}
+
+impl Value {
+    fn phoneme(self) -> Result<Option<String>, FieldError> {
+        match self.value {
+            Some(FieldValue::Phoneme(value)) => Ok(Some(value)),
+            None => Ok(None),
+            Some(_) => Err(FieldError::TypeMismatch(self.field, "phoneme")),
+        }
+    }
+
+    fn signed(self) -> Result<Option<i8>, FieldError> {
+        match self.value {
+            Some(FieldValue::Signed(value)) => Ok(Some(value)),
+            None => Ok(None),
+            Some(_) => Err(FieldError::TypeMismatch(self.field, "signed integer")),
+        }
+    }
+
+    fn unsigned(self) -> Result<Option<u8>, FieldError> {
+        match self.value {
+            Some(FieldValue::Unsigned(value)) => Ok(Some(value)),
+            None => Ok(None),
+            Some(_) => Err(FieldError::TypeMismatch(self.field, "unsigned integer")),
+        }
+    }
+
+    fn boolean(self) -> Result<Option<bool>, FieldError> {
+        match self.value {
+            Some(FieldValue::Boolean(value)) => Ok(Some(value)),
+            None => Ok(None),
+            Some(_) => Err(FieldError::TypeMismatch(self.field, "boolean")),
+        }
+    }
+
+    /// For `E5` and `G5`, whose logics are inverted.
+    fn inverted(self) -> Result<Option<bool>, FieldError> {
+        Ok(self.boolean()?.map(|value| !value))
+    }
+
+    fn required(self) -> Result<u8, FieldError> {
+        let field = self.field;
+        self.unsigned()?.ok_or(FieldError::Required(field))
+    }
+}
+
+/// Sets a required field of the block.
+fn set<B: Default, T>(
+    block: &mut Option<B>,
+    value: Option<T>,
+    field: impl FnOnce(&mut B) -> &mut T,
+) {
+    match value {
+        Some(value) => *field(block.get_or_insert_with(B::default)) = value,
+        None => *block = None,
+    }
+}
+
+/// Sets an optional field of the block.
+fn set_optional<B: Default, T>(
+    block: &mut Option<B>,
+    value: Option<T>,
+    field: impl FnOnce(&mut B) -> &mut Option<T>,
+) {
+    match (block.as_mut(), value) {
+        (Some(block), value) => *field(block) = value,
+        (None, Some(value)) => *field(block.insert(B::default())) = Some(value),
+        (None, None) => (),
+    }
+}
+
+/// Sets a field of the word, which is undefined if all the fields are undefined.
+fn set_word(
+    word: &mut Option<crate::Word>,
+    value: Option<u8>,
+    field: impl FnOnce(&mut crate::Word) -> &mut Option<u8>,
+) {
+    set_optional(word, value, field);
+    if word
+        .as_ref()
+        .is_some_and(|word| word.pos.is_none() && word.ctype.is_none() && word.cform.is_none())
+    {
+        *word = None;
+    }
+}
+
+/// Splits the serialized label into fields.
+pub(crate) fn split(mut label: &str) -> impl Iterator<Item = (FieldId, &str)> {
+    FieldId::ALL.into_iter().map(move |field| {
+        let (value, rest) = match field.delimiter() {
+            "" => (label, ""),
+            // Only if a phoneme contains a delimiter
+            delimiter => label.split_once(delimiter).unwrap_or((label, "")),
+        };
+        label = rest;
+        (field, value)
+    })
+}
@@ -1,3 +1,5 @@
+use alloc::string::String;
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -50,7 +52,7 @@ pub struct Phoneme {
 }
 
 /// `Mora` field of full-context label (`A` field).
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Mora {
     /// A1: the difference between accent type and position of the current mora identity
@@ -62,7 +64,7 @@ pub struct Mora {
 }
 
 /// `Word` field of full-context label (`B`, `C`, and `D` field).
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Word {
     /// B1/C1/D1: pos (part-of-speech) of the word
@@ -76,7 +78,7 @@ pub struct Word {
 /// `AccentPhrase` field of full-context label for current accent phrase (`F` field).
 ///
 /// F4 is undefined.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AccentPhraseCurrent {
     /// F1: the number of moras in the current accent phrase
@@ -98,7 +100,7 @@ pub struct AccentPhraseCurrent {
 /// `AccentPhrase` field of full-context label for previous or next accent phrase (`E` and `G` field).
 ///
 /// E4/G4 is undefined.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AccentPhrasePrevNext {
     /// E1/G1: the number of moras in the accent phrase
@@ -119,7 +121,7 @@ pub struct AccentPhrasePrevNext {
 }
 
 /// `BreathGroup` field of full-context label for current breath group (`I` field).
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BreathGroupCurrent {
     /// I1: the number of accent phrases in the current breath group
@@ -141,7 +143,7 @@ pub struct BreathGroupCurrent {
 }
 
 /// `BreathGroup` field of full-context label for previous or next breath group (`H` and `J` field).
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BreathGroupPrevNext {
     /// H1/J1: the number of accent phrases in the breath group
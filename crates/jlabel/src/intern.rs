@@ -0,0 +1,247 @@
+//! Interned representation of labels sharing a phoneme table
+//!
+//! [`InternedLabel`] holds the phonemes as [`PhonemeId`]s of a [`PhonemeTable`]
+//! instead of `String`s, so a corpus held in memory stores each phoneme only once,
+//! and phonemes are compared as integers.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::{Label, intern::PhonemeTable};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let labels: Vec<Label> = [
+//!     "xx^xx-sil+k=o/A:xx+xx+xx/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:xx_xx#xx_xx@xx_xx|xx_xx/G:2_1%0_xx_xx/H:xx_xx/I:xx-xx@xx+xx&xx-xx|xx+xx/J:1_2/K:1+1-2",
+//!     "xx^sil-k+o=sil/A:0+1+2/B:xx-xx_xx/C:02_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:2_1#0_xx@1_1|1_2/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-2@1+1&1-1|1+2/J:xx_xx/K:1+1-2",
+//! ]
+//! .iter()
+//! .map(|s| s.parse())
+//! .collect::<Result<_, _>>()?;
+//!
+//! let mut table = PhonemeTable::new();
+//! let interned = table.intern_labels(labels.iter().cloned());
+//! assert_eq!(table.len(), 3);
+//! assert_eq!(interned[0].phoneme.c, interned[1].phoneme.p1);
+//! assert_eq!(interned[1].phoneme.c, table.get("k"));
+//!
+//! assert_eq!(table.resolve_label(&interned[1]), labels[1]);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    AccentPhraseCurrent, AccentPhrasePrevNext, BreathGroupCurrent, BreathGroupPrevNext, Label,
+    Mora, Phoneme, Utterance, Word,
+};
+
+/// The handle of a phoneme in [`PhonemeTable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PhonemeId(u32);
+
+impl PhonemeId {
+    /// The index in [`PhonemeTable`], which is in the order of interning.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// The table of interned phonemes.
+///
+/// A table can be shared by the labels of many sequences.
+#[derive(Debug, Clone, Default)]
+pub struct PhonemeTable {
+    phonemes: Vec<String>,
+    ids: BTreeMap<String, PhonemeId>,
+}
+
+impl PhonemeTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of the phonemes.
+    pub fn len(&self) -> usize {
+        self.phonemes.len()
+    }
+
+    /// Returns `true` if there are no phonemes.
+    pub fn is_empty(&self) -> bool {
+        self.phonemes.is_empty()
+    }
+
+    /// Iterates over the phonemes with their ids in the order of interning.
+    pub fn iter(&self) -> impl Iterator<Item = (PhonemeId, &str)> {
+        self.phonemes
+            .iter()
+            .enumerate()
+            .map(|(index, phoneme)| (PhonemeId(index as u32), phoneme.as_str()))
+    }
+
+    /// Returns the id of the phoneme, adding it to the table if not exists.
+    pub fn intern(&mut self, phoneme: &str) -> PhonemeId {
+        if let Some(id) = self.ids.get(phoneme) {
+            return *id;
+        }
+        let id = PhonemeId(
+            u32::try_from(self.phonemes.len()).expect("The number of phonemes exceeds u32::MAX"),
+        );
+        self.phonemes.push(phoneme.to_string());
+        self.ids.insert(phoneme.to_string(), id);
+        id
+    }
+
+    /// Returns the id of the phoneme, or `None` if it is not in the table.
+    pub fn get(&self, phoneme: &str) -> Option<PhonemeId> {
+        self.ids.get(phoneme).copied()
+    }
+
+    /// Returns the phoneme of the id, or `None` if it is not of this table.
+    pub fn resolve(&self, id: PhonemeId) -> Option<&str> {
+        self.phonemes.get(id.index()).map(String::as_str)
+    }
+
+    /// Interns the phonemes of the label.
+    pub fn intern_label(&mut self, label: Label) -> InternedLabel {
+        let Label {
+            phoneme,
+            mora,
+            word_prev,
+            word_curr,
+            word_next,
+            accent_phrase_prev,
+            accent_phrase_curr,
+            accent_phrase_next,
+            breath_group_prev,
+            breath_group_curr,
+            breath_group_next,
+            utterance,
+        } = label;
+        let mut intern = |phoneme: Option<String>| phoneme.map(|p| self.intern(&p));
+        InternedLabel {
+            phoneme: InternedPhoneme {
+                p2: intern(phoneme.p2),
+                p1: intern(phoneme.p1),
+                c: intern(phoneme.c),
+                n1: intern(phoneme.n1),
+                n2: intern(phoneme.n2),
+            },
+            mora,
+            word_prev,
+            word_curr,
+            word_next,
+            accent_phrase_prev,
+            accent_phrase_curr,
+            accent_phrase_next,
+            breath_group_prev,
+            breath_group_curr,
+            breath_group_next,
+            utterance,
+        }
+    }
+
+    /// Interns the phonemes of the labels.
+    pub fn intern_labels(&mut self, labels: impl IntoIterator<Item = Label>) -> Vec<InternedLabel> {
+        labels
+            .into_iter()
+            .map(|label| self.intern_label(label))
+            .collect()
+    }
+
+    /// Resolves the phonemes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an id is not of this table.
+    pub fn resolve_phoneme(&self, phoneme: &InternedPhoneme) -> Phoneme {
+        let resolve = |id: Option<PhonemeId>| {
+            id.map(|id| {
+                self.resolve(id)
+                    .expect("The phoneme is not of this table")
+                    .to_string()
+            })
+        };
+        Phoneme {
+            p2: resolve(phoneme.p2),
+            p1: resolve(phoneme.p1),
+            c: resolve(phoneme.c),
+            n1: resolve(phoneme.n1),
+            n2: resolve(phoneme.n2),
+        }
+    }
+
+    /// Resolves the phonemes of the label.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the label is not interned with this table.
+    pub fn resolve_label(&self, label: &InternedLabel) -> Label {
+        Label {
+            phoneme: self.resolve_phoneme(&label.phoneme),
+            mora: label.mora.clone(),
+            word_prev: label.word_prev.clone(),
+            word_curr: label.word_curr.clone(),
+            word_next: label.word_next.clone(),
+            accent_phrase_prev: label.accent_phrase_prev.clone(),
+            accent_phrase_curr: label.accent_phrase_curr.clone(),
+            accent_phrase_next: label.accent_phrase_next.clone(),
+            breath_group_prev: label.breath_group_prev.clone(),
+            breath_group_curr: label.breath_group_curr.clone(),
+            breath_group_next: label.breath_group_next.clone(),
+            utterance: label.utterance.clone(),
+        }
+    }
+}
+
+/// [`Phoneme`] with interned phonemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InternedPhoneme {
+    /// P1: the phoneme identity before the previous phoneme
+    pub p2: Option<PhonemeId>,
+    /// P2: the previous phoneme identity
+    pub p1: Option<PhonemeId>,
+    /// P3: the current phoneme identity
+    pub c: Option<PhonemeId>,
+    /// P4: the next phoneme identity
+    pub n1: Option<PhonemeId>,
+    /// P5: the phoneme after the next phoneme identity
+    pub n2: Option<PhonemeId>,
+}
+
+/// [`Label`] with interned phonemes.
+///
+/// This is created by [`PhonemeTable::intern_label`],
+/// and converted back to [`Label`] by [`PhonemeTable::resolve_label`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InternedLabel {
+    /// Phoneme
+    pub phoneme: InternedPhoneme,
+    /// A: Mora
+    pub mora: Option<Mora>,
+    /// B: Previous Word
+    pub word_prev: Option<Word>,
+    /// C: Current Word
+    pub word_curr: Option<Word>,
+    /// D: Next Word
+    pub word_next: Option<Word>,
+    /// E: Previous Accent Phrase
+    pub accent_phrase_prev: Option<AccentPhrasePrevNext>,
+    /// F: Current Accent Phrase
+    pub accent_phrase_curr: Option<AccentPhraseCurrent>,
+    /// G: Next Accent Phrase
+    pub accent_phrase_next: Option<AccentPhrasePrevNext>,
+    /// H: Previous Breath Group
+    pub breath_group_prev: Option<BreathGroupPrevNext>,
+    /// I: Current Breath Group
+    pub breath_group_curr: Option<BreathGroupCurrent>,
+    /// J: Next Breath Group
+    pub breath_group_next: Option<BreathGroupPrevNext>,
+    /// K: Utterance
+    pub utterance: Utterance,
+}
@@ -0,0 +1,392 @@
+//! Kana notation with accent
+//!
+//! The notation is a katakana string of moras, with the following symbols:
+//!
+//! - `'` right after the mora with accent nucleus
+//! - `/` between accent phrases
+//! - `、` at pause between accent phrases
+//! - `？` at the end of interrogative accent phrase
+//!
+//! e.g. `コンニチワ'/ゲンキ'デスカ？`
+//!
+//! As the accent position of type 0 (heiban) accent phrase is the same as the number of moras
+//! in full-context label, `'` is written at the end of heiban accent phrase.
+//!
+//! [`to_kana`] renders label sequence into the notation, and [`from_kana`] generates label sequence from it.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::kana::{from_kana, to_kana};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let labels = from_kana("コンニチワ'/ゲンキ'デスカ？")?;
+//! assert_eq!(labels[1].to_string(), "xx^sil-k+o=N/A:-4+1+5/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_2|1_11/G:6_3%1_xx_1/H:xx_xx/I:2-11@1+1&1-2|1+11/J:xx_xx/K:1+2-11");
+//! assert_eq!(to_kana(&labels), "コンニチワ'/ゲンキ'デスカ？");
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    Label,
+    edit::{BreathGroup, LabelEditor, Phrase, template},
+    segment,
+};
+
+/// Errors from [`from_kana`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum KanaError {
+    /// The character is not a katakana mora nor a symbol of the notation,
+    /// or the symbol is at a wrong place.
+    #[error("Unexpected character {0:?} at {1}")]
+    UnexpectedCharacter(char, usize),
+    /// The accent phrase ending at the byte index has no mora.
+    #[error("Empty accent phrase at {0}")]
+    EmptyAccentPhrase(usize),
+    /// The accent phrase has more than one accent nucleus.
+    #[error("Multiple accent nuclei at {0}")]
+    MultipleAccentNuclei(usize),
+    /// The number of moras exceeds the maximum of full-context label (255).
+    #[error("Too many moras")]
+    TooManyMoras,
+}
+
+/// Moras in katakana and their consonant and vowel phonemes.
+///
+/// When rendering, the first one matching the phonemes is used.
+const MORAS: &[(&str, &str, &str)] = &[
+    ("ア", "", "a"),
+    ("イ", "", "i"),
+    ("ウ", "", "u"),
+    ("エ", "", "e"),
+    ("オ", "", "o"),
+    ("カ", "k", "a"),
+    ("キ", "k", "i"),
+    ("ク", "k", "u"),
+    ("ケ", "k", "e"),
+    ("コ", "k", "o"),
+    ("キャ", "ky", "a"),
+    ("キュ", "ky", "u"),
+    ("キェ", "ky", "e"),
+    ("キョ", "ky", "o"),
+    ("ガ", "g", "a"),
+    ("ギ", "g", "i"),
+    ("グ", "g", "u"),
+    ("ゲ", "g", "e"),
+    ("ゴ", "g", "o"),
+    ("ギャ", "gy", "a"),
+    ("ギュ", "gy", "u"),
+    ("ギェ", "gy", "e"),
+    ("ギョ", "gy", "o"),
+    ("サ", "s", "a"),
+    ("スィ", "s", "i"),
+    ("ス", "s", "u"),
+    ("セ", "s", "e"),
+    ("ソ", "s", "o"),
+    ("シャ", "sh", "a"),
+    ("シ", "sh", "i"),
+    ("シュ", "sh", "u"),
+    ("シェ", "sh", "e"),
+    ("ショ", "sh", "o"),
+    ("ザ", "z", "a"),
+    ("ズィ", "z", "i"),
+    ("ズ", "z", "u"),
+    ("ゼ", "z", "e"),
+    ("ゾ", "z", "o"),
+    ("ジャ", "j", "a"),
+    ("ジ", "j", "i"),
+    ("ジュ", "j", "u"),
+    ("ジェ", "j", "e"),
+    ("ジョ", "j", "o"),
+    ("タ", "t", "a"),
+    ("ティ", "t", "i"),
+    ("トゥ", "t", "u"),
+    ("テ", "t", "e"),
+    ("ト", "t", "o"),
+    ("テャ", "ty", "a"),
+    ("テュ", "ty", "u"),
+    ("テョ", "ty", "o"),
+    ("チャ", "ch", "a"),
+    ("チ", "ch", "i"),
+    ("チュ", "ch", "u"),
+    ("チェ", "ch", "e"),
+    ("チョ", "ch", "o"),
+    ("ツァ", "ts", "a"),
+    ("ツィ", "ts", "i"),
+    ("ツ", "ts", "u"),
+    ("ツェ", "ts", "e"),
+    ("ツォ", "ts", "o"),
+    ("ダ", "d", "a"),
+    ("ディ", "d", "i"),
+    ("ドゥ", "d", "u"),
+    ("デ", "d", "e"),
+    ("ド", "d", "o"),
+    ("デャ", "dy", "a"),
+    ("デュ", "dy", "u"),
+    ("デョ", "dy", "o"),
+    ("ナ", "n", "a"),
+    ("ニ", "n", "i"),
+    ("ヌ", "n", "u"),
+    ("ネ", "n", "e"),
+    ("ノ", "n", "o"),
+    ("ニャ", "ny", "a"),
+    ("ニュ", "ny", "u"),
+    ("ニェ", "ny", "e"),
+    ("ニョ", "ny", "o"),
+    ("ハ", "h", "a"),
+    ("ヒ", "h", "i"),
+    ("ホゥ", "h", "u"),
+    ("ヘ", "h", "e"),
+    ("ホ", "h", "o"),
+    ("ヒャ", "hy", "a"),
+    ("ヒュ", "hy", "u"),
+    ("ヒェ", "hy", "e"),
+    ("ヒョ", "hy", "o"),
+    ("ファ", "f", "a"),
+    ("フィ", "f", "i"),
+    ("フ", "f", "u"),
+    ("フェ", "f", "e"),
+    ("フォ", "f", "o"),
+    ("バ", "b", "a"),
+    ("ビ", "b", "i"),
+    ("ブ", "b", "u"),
+    ("ベ", "b", "e"),
+    ("ボ", "b", "o"),
+    ("ビャ", "by", "a"),
+    ("ビュ", "by", "u"),
+    ("ビェ", "by", "e"),
+    ("ビョ", "by", "o"),
+    ("パ", "p", "a"),
+    ("ピ", "p", "i"),
+    ("プ", "p", "u"),
+    ("ペ", "p", "e"),
+    ("ポ", "p", "o"),
+    ("ピャ", "py", "a"),
+    ("ピュ", "py", "u"),
+    ("ピェ", "py", "e"),
+    ("ピョ", "py", "o"),
+    ("マ", "m", "a"),
+    ("ミ", "m", "i"),
+    ("ム", "m", "u"),
+    ("メ", "m", "e"),
+    ("モ", "m", "o"),
+    ("ミャ", "my", "a"),
+    ("ミュ", "my", "u"),
+    ("ミェ", "my", "e"),
+    ("ミョ", "my", "o"),
+    ("ヤ", "y", "a"),
+    ("ユ", "y", "u"),
+    ("イェ", "y", "e"),
+    ("ヨ", "y", "o"),
+    ("ラ", "r", "a"),
+    ("リ", "r", "i"),
+    ("ル", "r", "u"),
+    ("レ", "r", "e"),
+    ("ロ", "r", "o"),
+    ("リャ", "ry", "a"),
+    ("リュ", "ry", "u"),
+    ("リェ", "ry", "e"),
+    ("リョ", "ry", "o"),
+    ("ワ", "w", "a"),
+    ("ウィ", "w", "i"),
+    ("ウェ", "w", "e"),
+    ("ウォ", "w", "o"),
+    ("ヴァ", "v", "a"),
+    ("ヴィ", "v", "i"),
+    ("ヴ", "v", "u"),
+    ("ヴェ", "v", "e"),
+    ("ヴォ", "v", "o"),
+    ("ン", "", "N"),
+    ("ッ", "", "cl"),
+    // Only for parsing
+    ("ー", "", ""),
+    ("ヂ", "j", "i"),
+    ("ヅ", "z", "u"),
+    ("ヲ", "", "o"),
+    ("ァ", "", "a"),
+    ("ィ", "", "i"),
+    ("ゥ", "", "u"),
+    ("ェ", "", "e"),
+    ("ォ", "", "o"),
+];
+
+/// Renders the label sequence into kana notation with accent.
+///
+/// Labels without mora (`sil`, `pau`) are not rendered,
+/// but `pau` between accent phrases is rendered as `、`.
+/// Devoiced vowels (e.g. `U`) are rendered as voiced ones,
+/// and moras that cannot be written in katakana are written in phonemes as they are.
+pub fn to_kana(labels: &[Label]) -> String {
+    let mut kana = String::new();
+    let mut prev_end = None;
+
+    for phrase in segment::phrases(labels) {
+        if let Some(prev_end) = prev_end {
+            let is_pause = labels[prev_end..phrase.labels.start]
+                .iter()
+                .any(|label| label.phoneme.c.as_deref() == Some("pau"));
+            kana.push(if is_pause { '、' } else { '/' });
+        }
+        prev_end = Some(phrase.labels.end);
+
+        let accent_phrase = labels[phrase.labels.start].accent_phrase_curr.as_ref();
+        let accent_position = accent_phrase.map_or(0, |ap| usize::from(ap.accent_position));
+
+        for (index, mora) in phrase.moras.iter().enumerate() {
+            let phonemes: Vec<&str> = labels[mora.clone()]
+                .iter()
+                .map(|label| label.phoneme.c.as_deref().unwrap_or_default())
+                .collect();
+            match phonemes[..] {
+                [consonant, vowel] => push_mora(&mut kana, consonant, vowel),
+                [vowel] => push_mora(&mut kana, "", vowel),
+                _ => phonemes.iter().for_each(|phoneme| kana.push_str(phoneme)),
+            }
+            if index + 1 == accent_position {
+                kana.push('\'');
+            }
+        }
+
+        if accent_phrase.is_some_and(|ap| ap.is_interrogative) {
+            kana.push('？');
+        }
+    }
+
+    kana
+}
+
+fn push_mora(kana: &mut String, consonant: &str, vowel: &str) {
+    let voiced = match vowel {
+        "A" | "I" | "U" | "E" | "O" => vowel.to_ascii_lowercase(),
+        vowel => vowel.to_string(),
+    };
+    match MORAS
+        .iter()
+        .find(|(_, c, v)| *c == consonant && *v == voiced)
+    {
+        Some((mora, _, _)) => kana.push_str(mora),
+        None => {
+            kana.push_str(consonant);
+            kana.push_str(vowel);
+        }
+    }
+}
+
+/// Generates label sequence from kana notation with accent.
+///
+/// Each breath group separated by `、` is joined by `pau`, and the whole sequence is enclosed by `sil`.
+/// Accent phrases without `'` are type 0 (heiban).
+/// `ー` is the same vowel as the previous mora.
+///
+/// As there is no information on words, the fields of words (`B`, `C`, `D`) are all `xx`.
+pub fn from_kana(kana: &str) -> Result<Vec<Label>, KanaError> {
+    let breath_groups = parse(kana)?;
+    let mora_count: usize = breath_groups
+        .iter()
+        .flat_map(|breath_group| &breath_group.phrases)
+        .map(|phrase| phrase.moras.len())
+        .sum();
+    if mora_count > usize::from(u8::MAX) {
+        return Err(KanaError::TooManyMoras);
+    }
+
+    let editor = LabelEditor {
+        head: template("sil"),
+        breath_groups,
+        tail: template("sil"),
+    };
+    Ok(editor.to_labels())
+}
+
+fn parse(kana: &str) -> Result<Vec<BreathGroup>, KanaError> {
+    let mut breath_groups = vec![BreathGroup {
+        pause: None,
+        phrases: vec![],
+    }];
+    let mut moras: Vec<Vec<Label>> = Vec::new();
+    let mut accent_position = None;
+    let mut is_interrogative = false;
+
+    let mut index = 0;
+    loop {
+        let c = kana[index..].chars().next();
+        if matches!(c, None | Some('/' | '、')) {
+            if moras.is_empty() {
+                return Err(KanaError::EmptyAccentPhrase(index));
+            }
+            let mora_count = moras.len() as u8;
+            let phrase = Phrase {
+                moras: core::mem::take(&mut moras),
+                accent_position: accent_position.take().unwrap_or(mora_count),
+                is_interrogative: core::mem::take(&mut is_interrogative),
+            };
+            breath_groups.last_mut().unwrap().phrases.push(phrase);
+        }
+
+        let Some(c) = c else {
+            break;
+        };
+        match c {
+            '/' => (),
+            '、' => breath_groups.push(BreathGroup {
+                pause: Some(template("pau")),
+                phrases: vec![],
+            }),
+            '\'' => {
+                if moras.is_empty() || is_interrogative {
+                    return Err(KanaError::UnexpectedCharacter(c, index));
+                }
+                if accent_position.is_some() {
+                    return Err(KanaError::MultipleAccentNuclei(index));
+                }
+                accent_position = Some(moras.len() as u8);
+            }
+            '？' if !moras.is_empty() && !is_interrogative => is_interrogative = true,
+            _ => {
+                let Some((mora, consonant, vowel)) = MORAS
+                    .iter()
+                    .filter(|(mora, _, _)| kana[index..].starts_with(mora))
+                    .max_by_key(|(mora, _, _)| mora.len())
+                else {
+                    return Err(KanaError::UnexpectedCharacter(c, index));
+                };
+                if is_interrogative {
+                    return Err(KanaError::UnexpectedCharacter(c, index));
+                }
+                let vowel = match *vowel {
+                    // Long vowel
+                    "" => match moras.last().and_then(|mora| mora.last()) {
+                        Some(label)
+                            if matches!(
+                                label.phoneme.c.as_deref(),
+                                Some("a" | "i" | "u" | "e" | "o")
+                            ) =>
+                        {
+                            label.phoneme.c.clone().unwrap()
+                        }
+                        _ => return Err(KanaError::UnexpectedCharacter(c, index)),
+                    },
+                    vowel => vowel.to_string(),
+                };
+                moras.push(
+                    [consonant, vowel.as_str()]
+                        .into_iter()
+                        .filter(|phoneme| !phoneme.is_empty())
+                        .map(template)
+                        .collect(),
+                );
+                index += mora.len();
+                continue;
+            }
+        }
+        index += c.len_utf8();
+    }
+
+    Ok(breath_groups)
+}
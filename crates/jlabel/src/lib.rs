@@ -1,3 +1,4 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
@@ -58,9 +59,37 @@
 //! # }
 //! ```
 
+extern crate alloc;
+
 mod fullcontext_label;
 mod parser;
+mod segment;
 mod serializer;
 
+pub mod binary;
+pub mod builder;
+pub mod codes;
+pub mod diff;
+pub mod edit;
+pub mod field;
+pub mod intern;
+pub mod kana;
+pub mod pitch;
+pub mod prosody;
+#[cfg(feature = "std")]
+pub mod reader;
+#[cfg(feature = "std")]
+pub mod table;
+
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "serde")]
+pub mod serde;
+
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
 pub use fullcontext_label::*;
 pub use parser::ParseError;
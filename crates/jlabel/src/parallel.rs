@@ -0,0 +1,67 @@
+//! Parallel parsing of label files with [`rayon`]
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! use jlabel::parallel::read_dir;
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! for (path, result) in read_dir("labels", "lab")? {
+//!     match result {
+//!         Ok(labels) => println!("{}: {} labels", path.display(), labels.len()),
+//!         Err(err) => eprintln!("{}: {err}", path.display()),
+//!     }
+//! }
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+};
+
+use rayon::prelude::*;
+
+use crate::{Label, reader::LabelReader, table::TableError};
+
+/// The result of reading a label file.
+pub type FileResult = Result<Vec<Label>, TableError>;
+
+/// Reads the label file, returning the first error if any.
+///
+/// The file is read with [`LabelReader`], so it may have comments and HTK timings.
+pub fn read_file(path: impl AsRef<Path>) -> FileResult {
+    let file = File::open(path)?;
+    LabelReader::new(BufReader::new(file))
+        .map(|result| result.map(|(_, label)| label))
+        .collect()
+}
+
+/// Reads the label files in parallel.
+///
+/// The results are in the same order as `paths`.
+/// The errors are collected for each file, and do not abort reading the other files.
+pub fn read_files<P: AsRef<Path> + Sync>(paths: &[P]) -> Vec<FileResult> {
+    paths.par_iter().map(read_file).collect()
+}
+
+/// Reads the files with the extension in the directory in parallel.
+///
+/// The subdirectories are not read.
+/// The results are sorted by the paths.
+pub fn read_dir(dir: impl AsRef<Path>, extension: &str) -> io::Result<Vec<(PathBuf, FileResult)>> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_file() && path.extension().is_some_and(|ext| ext == extension) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let results = read_files(&paths);
+    Ok(paths.into_iter().zip(results).collect())
+}
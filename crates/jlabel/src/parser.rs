@@ -1,4 +1,5 @@
-use std::{num::ParseIntError, str::FromStr};
+use alloc::string::{String, ToString};
+use core::{num::ParseIntError, str::FromStr};
 
 use crate::fullcontext_label::{
     AccentPhraseCurrent, AccentPhrasePrevNext, BreathGroupCurrent, BreathGroupPrevNext, Label,
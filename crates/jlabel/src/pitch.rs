@@ -0,0 +1,120 @@
+//! High/low pitch pattern of each mora
+//!
+//! The pitch is computed following the rules of Tokyo dialect:
+//!
+//! - The first mora is low, and the pitch rises at the second mora,
+//!   unless the accent nucleus is on the first mora.
+//! - The pitch falls right after the accent nucleus.
+//! - Accent phrases without a fall (type 0, heiban) stay high until the end.
+
+use alloc::vec::Vec;
+use core::{
+    fmt::{Display, Formatter, Result},
+    ops::Range,
+};
+
+use crate::{Label, segment};
+
+/// High or low pitch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pitch {
+    /// High pitch
+    High,
+    /// Low pitch
+    Low,
+}
+
+impl Display for Pitch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(match self {
+            Self::High => "H",
+            Self::Low => "L",
+        })
+    }
+}
+
+/// The pitch of a mora.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoraPitch {
+    /// The indices of labels in the mora.
+    pub labels: Range<usize>,
+    /// The pitch of the mora.
+    pub pitch: Pitch,
+}
+
+/// The pitch pattern of an accent phrase.
+///
+/// With [`Display`], the pattern is written as a sequence of `H` and `L`, e.g. `LHHLL`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccentPhrasePitch {
+    /// The indices of labels in the accent phrase.
+    pub labels: Range<usize>,
+    /// The accent position (`F2`).
+    pub accent_position: u8,
+    /// The number of moras (`F1`).
+    pub mora_count: u8,
+    /// The pitch of each mora.
+    pub moras: Vec<MoraPitch>,
+}
+
+impl AccentPhrasePitch {
+    /// Returns `true` if the accent phrase is type 0 (heiban), i.e. the pitch does not fall.
+    ///
+    /// In full-context label, type 0 is written as the accent position same as the number of moras.
+    pub fn is_heiban(&self) -> bool {
+        self.accent_position == 0 || self.accent_position >= self.mora_count
+    }
+}
+
+impl Display for AccentPhrasePitch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for mora in &self.moras {
+            write!(f, "{}", mora.pitch)?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes the pitch of each mora in the label sequence, grouped by accent phrases.
+///
+/// The pitch of each mora is determined by `A1`, the relative position from the accent nucleus.
+/// Labels without mora (`sil`, `pau`) are not included.
+pub fn pitch(labels: &[Label]) -> Vec<AccentPhrasePitch> {
+    segment::phrases(labels)
+        .into_iter()
+        .map(|phrase| {
+            let accent_phrase = labels[phrase.labels.start].accent_phrase_curr.as_ref();
+            let accent_position = accent_phrase.map_or(0, |ap| ap.accent_position);
+            let mora_count = accent_phrase.map_or(phrase.moras.len() as u8, |ap| ap.mora_count);
+
+            let moras = phrase
+                .moras
+                .into_iter()
+                .enumerate()
+                .map(|(index, range)| {
+                    // `segment::phrases` only includes labels with mora
+                    let mora = labels[range.start].mora.as_ref().unwrap();
+                    let pitch = match (index, accent_position) {
+                        (0, 1) => Pitch::High,
+                        (0, _) => Pitch::Low,
+                        _ if mora.relative_accent_position <= 0 || accent_position == 0 => {
+                            Pitch::High
+                        }
+                        _ => Pitch::Low,
+                    };
+                    MoraPitch {
+                        labels: range,
+                        pitch,
+                    }
+                })
+                .collect();
+
+            AccentPhrasePitch {
+                labels: phrase.labels,
+                accent_position,
+                mora_count,
+                moras,
+            }
+        })
+        .collect()
+}
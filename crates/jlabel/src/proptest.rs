@@ -0,0 +1,239 @@
+//! Implementations of proptest's [`Arbitrary`] generating structurally valid labels
+//!
+//! The generated labels are serialized and parsed back into the same labels.
+//!
+//! Requires `proptest` feature.
+
+use ::proptest::{
+    arbitrary::{Arbitrary, any},
+    option,
+    strategy::{BoxedStrategy, Strategy},
+};
+
+use crate::{
+    AccentPhraseCurrent, AccentPhrasePrevNext, BreathGroupCurrent, BreathGroupPrevNext, Label,
+    Mora, Phoneme, Utterance, Word,
+};
+
+/// Phoneme of 1 to 3 ASCII letters except for `xx`, which means empty.
+pub fn phoneme() -> impl Strategy<Value = String> {
+    "[a-zA-Z]{1,3}".prop_filter("xx means empty", |phoneme| phoneme != "xx")
+}
+
+fn phoneme_or_xx() -> impl Strategy<Value = Option<String>> {
+    option::of(phoneme())
+}
+
+impl Arbitrary for Phoneme {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            phoneme_or_xx(),
+            phoneme_or_xx(),
+            phoneme_or_xx(),
+            phoneme_or_xx(),
+            phoneme_or_xx(),
+        )
+            .prop_map(|(p2, p1, c, n1, n2)| Self { p2, p1, c, n1, n2 })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Mora {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<(i8, u8, u8)>()
+            .prop_map(
+                |(relative_accent_position, position_forward, position_backward)| Self {
+                    relative_accent_position,
+                    position_forward,
+                    position_backward,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for Word {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// POS is in two digits and the others are in a digit, as they are in the label.
+    /// At least one of the fields is not `None`, as `xx_xx_xx` is parsed as no word.
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            option::of(0..=99u8),
+            option::of(0..=9u8),
+            option::of(0..=9u8),
+        )
+            .prop_filter("xx_xx_xx means no word", |fields| {
+                *fields != (None, None, None)
+            })
+            .prop_map(|(pos, ctype, cform)| Self { pos, ctype, cform })
+            .boxed()
+    }
+}
+
+impl Arbitrary for AccentPhraseCurrent {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<(u8, u8, bool, u8, u8, u8, u8)>()
+            .prop_map(
+                |(
+                    mora_count,
+                    accent_position,
+                    is_interrogative,
+                    accent_phrase_position_forward,
+                    accent_phrase_position_backward,
+                    mora_position_forward,
+                    mora_position_backward,
+                )| Self {
+                    mora_count,
+                    accent_position,
+                    is_interrogative,
+                    accent_phrase_position_forward,
+                    accent_phrase_position_backward,
+                    mora_position_forward,
+                    mora_position_backward,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for AccentPhrasePrevNext {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<(u8, u8, bool, Option<bool>)>()
+            .prop_map(
+                |(mora_count, accent_position, is_interrogative, is_pause_insertion)| Self {
+                    mora_count,
+                    accent_position,
+                    is_interrogative,
+                    is_pause_insertion,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for BreathGroupCurrent {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<(u8, u8, u8, u8, u8, u8, u8, u8)>()
+            .prop_map(
+                |(
+                    accent_phrase_count,
+                    mora_count,
+                    breath_group_position_forward,
+                    breath_group_position_backward,
+                    accent_phrase_position_forward,
+                    accent_phrase_position_backward,
+                    mora_position_forward,
+                    mora_position_backward,
+                )| Self {
+                    accent_phrase_count,
+                    mora_count,
+                    breath_group_position_forward,
+                    breath_group_position_backward,
+                    accent_phrase_position_forward,
+                    accent_phrase_position_backward,
+                    mora_position_forward,
+                    mora_position_backward,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for BreathGroupPrevNext {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<(u8, u8)>()
+            .prop_map(|(accent_phrase_count, mora_count)| Self {
+                accent_phrase_count,
+                mora_count,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Utterance {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any::<(u8, u8, u8)>()
+            .prop_map(
+                |(breath_group_count, accent_phrase_count, mora_count)| Self {
+                    breath_group_count,
+                    accent_phrase_count,
+                    mora_count,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for Label {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let words = any::<(Option<Word>, Option<Word>, Option<Word>)>();
+        let accent_phrases = any::<(
+            Option<AccentPhrasePrevNext>,
+            Option<AccentPhraseCurrent>,
+            Option<AccentPhrasePrevNext>,
+        )>();
+        let breath_groups = any::<(
+            Option<BreathGroupPrevNext>,
+            Option<BreathGroupCurrent>,
+            Option<BreathGroupPrevNext>,
+        )>();
+        (
+            any::<Phoneme>(),
+            any::<Option<Mora>>(),
+            words,
+            accent_phrases,
+            breath_groups,
+            any::<Utterance>(),
+        )
+            .prop_map(
+                |(
+                    phoneme,
+                    mora,
+                    (word_prev, word_curr, word_next),
+                    (accent_phrase_prev, accent_phrase_curr, accent_phrase_next),
+                    (breath_group_prev, breath_group_curr, breath_group_next),
+                    utterance,
+                )| Self {
+                    phoneme,
+                    mora,
+                    word_prev,
+                    word_curr,
+                    word_next,
+                    accent_phrase_prev,
+                    accent_phrase_curr,
+                    accent_phrase_next,
+                    breath_group_prev,
+                    breath_group_curr,
+                    breath_group_next,
+                    utterance,
+                },
+            )
+            .boxed()
+    }
+}
@@ -0,0 +1,100 @@
+//! Phoneme sequence with prosody symbols
+//!
+//! This is the format used to train end-to-end TTS models such as in ESPnet (`pyopenjtalk_prosody`),
+//! and the symbols are the same as those by `pp_symbols` of pyopenjtalk:
+//!
+//! - `^` at the beginning of sentence
+//! - `$` at the end of sentence, or `?` at the end of interrogative sentence
+//! - `_` at pause
+//! - `#` at accent phrase boundary
+//! - `[` where the pitch rises
+//! - `]` where the pitch falls (accent nucleus)
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::{kana::from_kana, prosody::prosody};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let labels = from_kana("コンニチワ、ゲ'ンキデスカ？")?;
+//! assert_eq!(
+//!     prosody(&labels, true).join(" "),
+//!     "^ k o [ N n i ch i w a _ g e ] N k i d e s u k a ?"
+//! );
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::Label;
+
+/// Converts the label sequence into phonemes with prosody symbols.
+///
+/// The symbols are determined by `A1`, `A2`, `A3` and `F1` of each label and `A2` of the next label,
+/// and by `E3` of the last `sil` for the end of sentence.
+/// If `drop_unvoiced_vowels` is `true`, unvoiced vowels (e.g. `U`) are written in lowercase.
+pub fn prosody(labels: &[Label], drop_unvoiced_vowels: bool) -> Vec<&str> {
+    let mut symbols = Vec::with_capacity(labels.len() * 2);
+
+    for (index, label) in labels.iter().enumerate() {
+        let phoneme = match label.phoneme.c.as_deref() {
+            Some("sil") if index == 0 => {
+                symbols.push("^");
+                continue;
+            }
+            Some("sil") => {
+                let accent_phrase = label.accent_phrase_prev.as_ref();
+                match accent_phrase.map(|ap| ap.is_interrogative) {
+                    Some(false) => symbols.push("$"),
+                    Some(true) => symbols.push("?"),
+                    None => (),
+                }
+                continue;
+            }
+            Some("pau") => {
+                symbols.push("_");
+                continue;
+            }
+            Some(phoneme) if drop_unvoiced_vowels => match phoneme {
+                "A" => "a",
+                "I" => "i",
+                "U" => "u",
+                "E" => "e",
+                "O" => "o",
+                phoneme => phoneme,
+            },
+            Some(phoneme) => phoneme,
+            None => "xx",
+        };
+        symbols.push(phoneme);
+
+        // xx is regarded as -50 as in pyopenjtalk
+        let mora = label.mora.as_ref();
+        let a1 = mora.map_or(-50, |mora| i16::from(mora.relative_accent_position));
+        let a2 = mora.map_or(-50, |mora| i16::from(mora.position_forward));
+        let a3 = mora.map_or(-50, |mora| i16::from(mora.position_backward));
+        let f1 = label
+            .accent_phrase_curr
+            .as_ref()
+            .map_or(-50, |ap| i16::from(ap.mora_count));
+        let a2_next = labels
+            .get(index + 1)
+            .and_then(|next| next.mora.as_ref())
+            .map_or(-50, |mora| i16::from(mora.position_forward));
+
+        let is_mora_end = matches!(
+            phoneme,
+            "a" | "i" | "u" | "e" | "o" | "A" | "I" | "U" | "E" | "O" | "N" | "cl"
+        );
+        if a3 == 1 && a2_next == 1 && is_mora_end {
+            symbols.push("#");
+        } else if a1 == 0 && a2_next == a2 + 1 && a2 != f1 {
+            symbols.push("]");
+        } else if a2 == 1 && a2_next == 2 {
+            symbols.push("[");
+        }
+    }
+
+    symbols
+}
@@ -0,0 +1,103 @@
+//! Streaming reader of label files
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::{reader::LabelReader, table::TableError};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let text = "\
+//! ## utterance 1
+//! 0 3000000 xx^xx-sil+k=o/A:xx+xx+xx/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:xx_xx#xx_xx@xx_xx|xx_xx/G:2_1%0_xx_xx/H:xx_xx/I:xx-xx@xx+xx&xx-xx|xx+xx/J:1_2/K:1+1-2
+//! broken
+//!
+//! xx^sil-k+o=sil/A:0+1+2/B:xx-xx_xx/C:02_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:2_1#0_xx@1_1|1_2/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-2@1+1&1-1|1+2/J:xx_xx/K:1+1-2
+//! ";
+//!
+//! let mut reader = LabelReader::new(text.as_bytes());
+//! let (line, label) = reader.next().unwrap()?;
+//! assert_eq!((line, label.phoneme.c.as_deref()), (2, Some("sil")));
+//! assert!(matches!(reader.next(), Some(Err(TableError::Parse(3, _)))));
+//! let (line, label) = reader.next().unwrap()?;
+//! assert_eq!((line, label.phoneme.c.as_deref()), (5, Some("k")));
+//! assert!(reader.next().is_none());
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use std::io::{BufRead, ErrorKind};
+
+use crate::{
+    Label,
+    table::{Row, TableError, parse_htk_line},
+};
+
+/// An iterator over the labels of a file, one per line,
+/// yielding the line numbers (starting from 1) with the labels.
+///
+/// Empty lines and comment lines starting with `#` are skipped.
+/// The lines may have HTK timings (`start end label`),
+/// which are available through [`LabelReader::next_row`].
+///
+/// The errors are yielded without stopping the iteration,
+/// so that the following lines are read.
+/// The iteration stops at the end of input or an IO error other than invalid UTF-8.
+/// The line buffer is reused for all the lines.
+#[derive(Debug)]
+pub struct LabelReader<R> {
+    reader: R,
+    line: String,
+    line_number: usize,
+    finished: bool,
+}
+
+impl<R: BufRead> LabelReader<R> {
+    /// Creates a reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            line_number: 0,
+            finished: false,
+        }
+    }
+
+    /// Reads the next label with its timings.
+    pub fn next_row(&mut self) -> Option<Result<(usize, Row), TableError>> {
+        while !self.finished {
+            self.line.clear();
+            self.line_number += 1;
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => self.finished = true,
+                Ok(_) => {
+                    let line = self.line.trim();
+                    if line.is_empty() || line.starts_with('#') {
+                        continue;
+                    }
+                    return Some(
+                        parse_htk_line(self.line_number, line).map(|row| (self.line_number, row)),
+                    );
+                }
+                Err(err) => {
+                    self.finished = err.kind() != ErrorKind::InvalidData;
+                    return Some(Err(err.into()));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> Iterator for LabelReader<R> {
+    type Item = Result<(usize, Label), TableError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_row()
+            .map(|row| row.map(|(line_number, row)| (line_number, row.label)))
+    }
+}
@@ -0,0 +1,69 @@
+//! Grouping of label sequence into accent phrases and moras
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::Label;
+
+/// An accent phrase in label sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Phrase {
+    /// The indices of labels in the accent phrase.
+    pub labels: Range<usize>,
+    /// The indices of labels in each mora.
+    pub moras: Vec<Range<usize>>,
+}
+
+/// Groups the labels into accent phrases and moras.
+///
+/// Labels without mora (`sil`, `pau`) do not belong to any accent phrase.
+/// Consecutive labels belong to the same accent phrase if they have the same position
+/// of accent phrase and breath group, and to the same mora if they also have the same `A2`.
+pub(crate) fn phrases(labels: &[Label]) -> Vec<Phrase> {
+    let mut phrases: Vec<Phrase> = Vec::new();
+    let mut prev: Option<&Label> = None;
+
+    for (index, label) in labels.iter().enumerate() {
+        let Some(mora) = &label.mora else {
+            prev = None;
+            continue;
+        };
+
+        match (prev, phrases.last_mut()) {
+            (Some(prev), Some(phrase)) if is_same_phrase(prev, label) => {
+                phrase.labels.end = index + 1;
+                match (&prev.mora, phrase.moras.last_mut()) {
+                    (Some(prev_mora), Some(last))
+                        if prev_mora.position_forward == mora.position_forward =>
+                    {
+                        last.end = index + 1;
+                    }
+                    _ => phrase.moras.push(index..index + 1),
+                }
+            }
+            _ => phrases.push(Phrase {
+                labels: index..index + 1,
+                moras: core::iter::once(index..index + 1).collect(),
+            }),
+        }
+        prev = Some(label);
+    }
+
+    phrases
+}
+
+fn is_same_phrase(a: &Label, b: &Label) -> bool {
+    let accent_phrase = |label: &Label| {
+        label
+            .accent_phrase_curr
+            .as_ref()
+            .map(|ap| ap.accent_phrase_position_forward)
+    };
+    let breath_group = |label: &Label| {
+        label
+            .breath_group_curr
+            .as_ref()
+            .map(|bg| bg.breath_group_position_forward)
+    };
+    accent_phrase(a) == accent_phrase(b) && breath_group(a) == breath_group(b)
+}
@@ -0,0 +1,228 @@
+//! Alternative forms of [`Label`] for serde
+//!
+//! These modules are used with `#[serde(with = "...")]`.
+//!
+//! - [`as_string`]: the full-context label string
+//! - [`as_map`]: a map from the codes of fields to their values, omitting the undefined fields
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::Label;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "jlabel::serde::as_string")]
+//!     label: Label,
+//! }
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let json = r#"{"label":"xx^sil-k+o=N/A:-4+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_5#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5"}"#;
+//! let config: Config = serde_json::from_str(json)?;
+//! assert_eq!(config.label.mora.as_ref().unwrap().relative_accent_position, -4);
+//! assert_eq!(serde_json::to_string(&config)?, json);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Formatter};
+
+use ::serde::{
+    Deserialize, Deserializer, Serializer,
+    de::{self, MapAccess, Visitor},
+    ser::SerializeMap,
+};
+
+use crate::{
+    Label, Phoneme, Utterance,
+    field::{FieldId, FieldValue},
+};
+
+/// Serializes [`Label`] as the full-context label string, and deserializes it by parsing.
+pub mod as_string {
+    use super::*;
+
+    /// Serializes the label as string.
+    pub fn serialize<S: Serializer>(label: &Label, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(label)
+    }
+
+    /// Deserializes the label from string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Label, D::Error> {
+        deserializer.deserialize_str(StringVisitor)
+    }
+
+    struct StringVisitor;
+
+    impl Visitor<'_> for StringVisitor {
+        type Value = Label;
+
+        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+            formatter.write_str("a full-context label string")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            v.parse().map_err(E::custom)
+        }
+    }
+}
+
+/// Serializes [`Label`] as a map from the codes of fields to their values as in full-context label,
+/// e.g. `{"P3": "k", "A1": -4, "F3": 0, ...}`.
+///
+/// The undefined fields are omitted, and `K1` to `K3` are required to deserialize.
+pub mod as_map {
+    use super::*;
+
+    /// Serializes the label as map.
+    pub fn serialize<S: Serializer>(label: &Label, serializer: S) -> Result<S::Ok, S::Error> {
+        let fields: Vec<(FieldId, FieldValue)> = label
+            .fields()
+            .filter_map(|(field, value)| Some((field, value?)))
+            .collect();
+        let mut map = serializer.serialize_map(Some(fields.len()))?;
+        for (field, value) in fields {
+            match value {
+                FieldValue::Phoneme(value) => map.serialize_entry(field.name(), &value)?,
+                FieldValue::Signed(value) => map.serialize_entry(field.name(), &value)?,
+                FieldValue::Unsigned(value) => map.serialize_entry(field.name(), &value)?,
+                FieldValue::Boolean(value) => {
+                    map.serialize_entry(field.name(), &u8::from(value))?
+                }
+            }
+        }
+        map.end()
+    }
+
+    /// Deserializes the label from map.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Label, D::Error> {
+        deserializer.deserialize_map(MapVisitor)
+    }
+
+    struct MapVisitor;
+
+    impl<'de> Visitor<'de> for MapVisitor {
+        type Value = Label;
+
+        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+            formatter.write_str("a map from the codes of fields to their values")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut label = Label {
+                phoneme: Phoneme {
+                    p2: None,
+                    p1: None,
+                    c: None,
+                    n1: None,
+                    n2: None,
+                },
+                mora: None,
+                word_prev: None,
+                word_curr: None,
+                word_next: None,
+                accent_phrase_prev: None,
+                accent_phrase_curr: None,
+                accent_phrase_next: None,
+                breath_group_prev: None,
+                breath_group_curr: None,
+                breath_group_next: None,
+                utterance: Utterance {
+                    breath_group_count: 0,
+                    accent_phrase_count: 0,
+                    mora_count: 0,
+                },
+            };
+            let mut utterance = [false; 3];
+
+            while let Some(key) = map.next_key::<String>()? {
+                let field: FieldId = key.parse().map_err(de::Error::custom)?;
+                let value = match map.next_value::<Option<MapValue>>()? {
+                    Some(value) => Some(value.into_field_value(field).map_err(de::Error::custom)?),
+                    None => None,
+                };
+                label.set_field(field, value).map_err(de::Error::custom)?;
+                match field {
+                    FieldId::K1 => utterance[0] = true,
+                    FieldId::K2 => utterance[1] = true,
+                    FieldId::K3 => utterance[2] = true,
+                    _ => (),
+                }
+            }
+
+            for (field, found) in ["K1", "K2", "K3"].into_iter().zip(utterance) {
+                if !found {
+                    return Err(de::Error::missing_field(field));
+                }
+            }
+            Ok(label)
+        }
+    }
+
+    /// The value in map before converted to [`FieldValue`] of the field.
+    enum MapValue {
+        String(String),
+        Integer(i64),
+    }
+
+    impl MapValue {
+        fn into_field_value(self, field: FieldId) -> Result<FieldValue, String> {
+            use FieldId::*;
+
+            let out_of_range = |value: i64| format!("{field} is out of range: {value}");
+            match (field, self) {
+                (P1 | P2 | P3 | P4 | P5, Self::String(value)) => Ok(FieldValue::Phoneme(value)),
+                (A1, Self::Integer(value)) => i8::try_from(value)
+                    .map(FieldValue::Signed)
+                    .map_err(|_| out_of_range(value)),
+                (E3 | E5 | F3 | G3 | G5, Self::Integer(value)) => match value {
+                    0 => Ok(FieldValue::Boolean(false)),
+                    1 => Ok(FieldValue::Boolean(true)),
+                    value => Err(out_of_range(value)),
+                },
+                (P1 | P2 | P3 | P4 | P5, _) | (_, Self::String(_)) => {
+                    Err(format!("Type mismatch of {field}"))
+                }
+                (_, Self::Integer(value)) => u8::try_from(value)
+                    .map(FieldValue::Unsigned)
+                    .map_err(|_| out_of_range(value)),
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for MapValue {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(MapValueVisitor)
+        }
+    }
+
+    struct MapValueVisitor;
+
+    impl Visitor<'_> for MapValueVisitor {
+        type Value = MapValue;
+
+        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+            formatter.write_str("a string or an integer")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(MapValue::String(v.to_string()))
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            Ok(MapValue::Integer(v))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            i64::try_from(v)
+                .map(MapValue::Integer)
+                .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+        }
+    }
+}
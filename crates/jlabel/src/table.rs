@@ -0,0 +1,319 @@
+//! Tabular export and import of label sequences as CSV or TSV
+//!
+//! The table has a header row and one column per field (`P1` to `K3`),
+//! optionally preceded by `file_id`, `start` and `end` columns.
+//! The values are written as in full-context label, and undefined values (`xx`) are empty cells.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! use jlabel::table::{Delimiter, TableOptions, read_table, rows_from_htk, write_table};
+//!
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! let lab = "0 3000000 xx^xx-sil+k=o/A:xx+xx+xx/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:xx_xx#xx_xx@xx_xx|xx_xx/G:2_1%0_xx_xx/H:xx_xx/I:xx-xx@xx+xx&xx-xx|xx+xx/J:1_2/K:1+1-2";
+//! let rows = rows_from_htk(Some("utt1"), lab)?;
+//!
+//! let options = TableOptions {
+//!     file_id: true,
+//!     timings: true,
+//!     ..Default::default()
+//! };
+//! let mut csv = Vec::new();
+//! write_table(&mut csv, &rows, &options)?;
+//! let csv = String::from_utf8(csv)?;
+//! assert!(csv.starts_with("file_id,start,end,P1,P2,P3,P4,P5,A1,"));
+//! assert!(csv.contains("\nutt1,0,3000000,,,sil,k,o,,,,"));
+//!
+//! assert_eq!(read_table(csv.as_bytes(), Delimiter::Comma)?, rows);
+//! #
+//! #     Ok(())
+//! # }
+//! ```
+
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    Label, ParseError,
+    field::{FieldId, split},
+};
+
+/// Errors from reading tables and label files.
+#[derive(Debug, thiserror::Error)]
+pub enum TableError {
+    /// Failed to read.
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    /// The table has no header row.
+    #[error("Header not found")]
+    NoHeader,
+    /// The header has a column which is not a field.
+    #[error("Unknown column {0:?}")]
+    UnknownColumn(String),
+    /// The header does not have the column of the field.
+    #[error("Column {0} not found")]
+    MissingColumn(FieldId),
+    /// The row does not have the same number of cells as the header.
+    #[error("Line {0}: number of cells does not match the header")]
+    CellCount(usize),
+    /// The time is not an integer.
+    #[error("Line {0}: invalid time")]
+    InvalidTime(usize),
+    /// The line of HTK label is neither `label` nor `start end label`.
+    #[error("Line {0}: invalid HTK label")]
+    InvalidHtk(usize),
+    /// The label could not be parsed.
+    #[error("Line {0}: {1}")]
+    Parse(usize, ParseError),
+}
+
+/// The delimiter of cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Delimiter {
+    /// Comma-separated values
+    #[default]
+    Comma,
+    /// Tab-separated values
+    Tab,
+}
+
+impl Delimiter {
+    fn as_char(self) -> char {
+        match self {
+            Self::Comma => ',',
+            Self::Tab => '\t',
+        }
+    }
+}
+
+/// Options of [`write_table`].
+#[derive(Debug, Clone, Default)]
+pub struct TableOptions {
+    /// The delimiter of cells.
+    pub delimiter: Delimiter,
+    /// Whether to write `file_id` column.
+    pub file_id: bool,
+    /// Whether to write `start` and `end` columns.
+    pub timings: bool,
+}
+
+/// A row of table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// The identifier of the file the label belongs to.
+    pub file_id: Option<String>,
+    /// The start time in HTK units (100 ns).
+    pub start: Option<u64>,
+    /// The end time in HTK units (100 ns).
+    pub end: Option<u64>,
+    /// The label.
+    pub label: Label,
+}
+
+impl From<Label> for Row {
+    fn from(label: Label) -> Self {
+        Self {
+            file_id: None,
+            start: None,
+            end: None,
+            label,
+        }
+    }
+}
+
+/// Reads the lines of HTK label file, each of which is `label` or `start end label`.
+///
+/// Empty lines are skipped.
+pub fn rows_from_htk(file_id: Option<&str>, text: &str) -> Result<Vec<Row>, TableError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let mut row = parse_htk_line(index + 1, line)?;
+            row.file_id = file_id.map(str::to_string);
+            Ok(row)
+        })
+        .collect()
+}
+
+/// Parses a line of HTK label file, which is `label` or `start end label`.
+pub(crate) fn parse_htk_line(line_number: usize, line: &str) -> Result<Row, TableError> {
+    let mut cells = line.split_whitespace();
+    let (start, end, label) = match (cells.next(), cells.next(), cells.next(), cells.next()) {
+        (Some(label), None, None, None) => (None, None, label),
+        (Some(start), Some(end), Some(label), None) => {
+            let time = |cell: &str| {
+                cell.parse()
+                    .map_err(|_| TableError::InvalidTime(line_number))
+            };
+            (Some(time(start)?), Some(time(end)?), label)
+        }
+        _ => return Err(TableError::InvalidHtk(line_number)),
+    };
+    Ok(Row {
+        file_id: None,
+        start,
+        end,
+        label: label
+            .parse()
+            .map_err(|err| TableError::Parse(line_number, err))?,
+    })
+}
+
+/// Writes the rows as a table with a header row.
+///
+/// The `file_id`, `start` and `end` columns are written only if enabled in `options`,
+/// and are empty for the rows without them.
+pub fn write_table<'a, W: Write>(
+    mut writer: W,
+    rows: impl IntoIterator<Item = &'a Row>,
+    options: &TableOptions,
+) -> io::Result<()> {
+    let delimiter = options.delimiter.as_char();
+
+    let mut header: Vec<&str> = Vec::with_capacity(53);
+    if options.file_id {
+        header.push("file_id");
+    }
+    if options.timings {
+        header.extend(["start", "end"]);
+    }
+    header.extend(FieldId::ALL.iter().map(|field| field.name()));
+    write_line(&mut writer, header, delimiter)?;
+
+    for row in rows {
+        let mut cells: Vec<String> = Vec::with_capacity(53);
+        if options.file_id {
+            cells.push(row.file_id.clone().unwrap_or_default());
+        }
+        if options.timings {
+            for time in [row.start, row.end] {
+                cells.push(time.map(|time| time.to_string()).unwrap_or_default());
+            }
+        }
+        let label = row.label.to_string();
+        cells.extend(split(&label).map(|(_, value)| match value {
+            "xx" => String::new(),
+            value => value.to_string(),
+        }));
+        write_line(&mut writer, cells.iter().map(String::as_str), delimiter)?;
+    }
+    Ok(())
+}
+
+/// Reads the table written by [`write_table`].
+///
+/// The columns may be in any order, and `file_id`, `start` and `end` columns are optional.
+/// Empty lines are skipped.
+pub fn read_table<R: BufRead>(reader: R, delimiter: Delimiter) -> Result<Vec<Row>, TableError> {
+    let delimiter = delimiter.as_char();
+    let mut lines = reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.as_ref().is_ok_and(|line| line.is_empty()));
+
+    let (_, header) = lines.next().ok_or(TableError::NoHeader)?;
+    let header = split_line(&header?, delimiter);
+
+    let mut file_id = None;
+    let mut start = None;
+    let mut end = None;
+    let mut fields = [None; 50];
+    for (column, name) in header.iter().enumerate() {
+        match name.as_str() {
+            "file_id" => file_id = Some(column),
+            "start" => start = Some(column),
+            "end" => end = Some(column),
+            name => {
+                let field: FieldId = name
+                    .parse()
+                    .map_err(|_| TableError::UnknownColumn(name.to_string()))?;
+                fields[field as usize] = Some(column);
+            }
+        }
+    }
+    let fields: Vec<(FieldId, usize)> = FieldId::ALL
+        .into_iter()
+        .map(|field| {
+            fields[field as usize]
+                .map(|column| (field, column))
+                .ok_or(TableError::MissingColumn(field))
+        })
+        .collect::<Result<_, _>>()?;
+
+    lines
+        .map(|(index, line)| {
+            let line_number = index + 1;
+            let cells = split_line(&line?, delimiter);
+            if cells.len() != header.len() {
+                return Err(TableError::CellCount(line_number));
+            }
+            let cell = |column: Option<usize>| {
+                column
+                    .map(|column| cells[column].as_str())
+                    .filter(|cell| !cell.is_empty())
+            };
+            let time = |column: Option<usize>| {
+                cell(column)
+                    .map(|cell| cell.parse())
+                    .transpose()
+                    .map_err(|_| TableError::InvalidTime(line_number))
+            };
+
+            let mut label = String::new();
+            for &(field, column) in &fields {
+                label.push_str(cell(Some(column)).unwrap_or("xx"));
+                label.push_str(field.delimiter());
+            }
+
+            Ok(Row {
+                file_id: cell(file_id).map(str::to_string),
+                start: time(start)?,
+                end: time(end)?,
+                label: label
+                    .parse()
+                    .map_err(|err| TableError::Parse(line_number, err))?,
+            })
+        })
+        .collect()
+}
+
+fn write_line<'a, W: Write>(
+    writer: &mut W,
+    cells: impl IntoIterator<Item = &'a str>,
+    delimiter: char,
+) -> io::Result<()> {
+    for (index, cell) in cells.into_iter().enumerate() {
+        if index > 0 {
+            write!(writer, "{delimiter}")?;
+        }
+        if cell.contains([delimiter, '"', '\n', '\r']) {
+            write!(writer, "\"{}\"", cell.replace('"', "\"\""))?;
+        } else {
+            writer.write_all(cell.as_bytes())?;
+        }
+    }
+    writeln!(writer)
+}
+
+/// Splits the line into cells, unquoting the quoted ones.
+///
+/// Line breaks in quoted cells are not supported.
+fn split_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if cell.is_empty() => quoted = true,
+            c if c == delimiter && !quoted => cells.push(std::mem::take(&mut cell)),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    cells
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use jlabel::{Label, arbitrary::seeded_bytes};
+
+#[test]
+fn round_trip() {
+    let data = seeded_bytes(1, 1 << 16);
+
+    let mut u = Unstructured::new(&data);
+    let mut count = 0;
+    while let Ok(label) = Label::arbitrary(&mut u) {
+        if u.is_empty() {
+            break;
+        }
+        let serialized = label.to_string();
+        assert_eq!(serialized.parse::<Label>().unwrap(), label, "{serialized}");
+        count += 1;
+    }
+    assert!(count > 100);
+}
@@ -0,0 +1,86 @@
+mod fixtures;
+use fixtures::fixtures;
+use jlabel::{
+    Label,
+    binary::{BinaryError, LabelsView, VERSION, decode, encode},
+};
+
+fn labels() -> Vec<Label> {
+    fixtures().into_iter().map(|(_, label)| label).collect()
+}
+
+#[test]
+fn roundtrip() {
+    let labels = labels();
+    let bytes = encode(&labels).unwrap();
+    assert_eq!(&bytes[..4], b"JLBL");
+    assert_eq!(bytes.len() % 8, 0);
+    assert_eq!(decode(&bytes).unwrap(), labels);
+
+    let view = LabelsView::new(&bytes).unwrap();
+    assert_eq!(view.len(), labels.len());
+    assert_eq!(view.phonemes()[..4], ["sil", "k", "o", "N"]);
+    let phonemes: std::collections::HashSet<_> = view.phonemes().iter().collect();
+    assert_eq!(phonemes.len(), view.phonemes().len());
+    assert_eq!(view.get(5), Ok(Some(labels[5].clone())));
+    assert_eq!(view.get(labels.len()), Ok(None));
+    assert_eq!(view.iter().collect::<Result<Vec<_>, _>>().unwrap(), labels);
+
+    let mut label = labels[1].clone();
+    label.mora.as_mut().unwrap().relative_accent_position = i8::MIN;
+    label.word_curr.as_mut().unwrap().cform = Some(u8::MAX);
+    label.word_next = Some(jlabel::Word {
+        pos: None,
+        ctype: Some(0),
+        cform: None,
+    });
+    label.accent_phrase_next = Some(jlabel::AccentPhrasePrevNext {
+        mora_count: 3,
+        accent_position: 1,
+        is_interrogative: true,
+        is_pause_insertion: Some(false),
+    });
+    assert_eq!(decode(&encode(&[label.clone()]).unwrap()).unwrap(), [label]);
+
+    let empty = encode(&[]).unwrap();
+    assert!(LabelsView::new(&empty).unwrap().is_empty());
+}
+
+#[test]
+fn errors() {
+    let labels = labels();
+    let bytes = encode(&labels).unwrap();
+
+    assert_eq!(decode(&bytes[..8]), Err(BinaryError::UnexpectedEnd));
+    assert_eq!(
+        decode(&bytes[..bytes.len() - 1]),
+        Err(BinaryError::UnexpectedEnd)
+    );
+
+    let mut invalid = bytes.clone();
+    invalid[0] = b'X';
+    assert_eq!(decode(&invalid), Err(BinaryError::InvalidMagic));
+
+    let mut invalid = bytes.clone();
+    invalid[4..6].copy_from_slice(&(VERSION + 1).to_le_bytes());
+    assert_eq!(
+        decode(&invalid),
+        Err(BinaryError::UnsupportedVersion(VERSION + 1))
+    );
+
+    let mut invalid = bytes.clone();
+    let last = invalid.len() - 56;
+    invalid[last..last + 2].copy_from_slice(&0xFFFEu16.to_le_bytes());
+    let view = LabelsView::new(&invalid).unwrap();
+    let index = labels.len() - 1;
+    assert_eq!(view.get(index), Err(BinaryError::UnknownPhoneme(index)));
+    assert_eq!(view.get(index + 1), Ok(None));
+    assert_eq!(decode(&invalid), Err(BinaryError::UnknownPhoneme(index)));
+
+    let mut label = labels[0].clone();
+    label.phoneme.c = Some("a".repeat(256));
+    assert_eq!(
+        encode(&[label]),
+        Err(BinaryError::PhonemeTooLong("a".repeat(256)))
+    );
+}
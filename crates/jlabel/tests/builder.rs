@@ -0,0 +1,162 @@
+mod fixtures;
+use fixtures::fixtures;
+use jlabel::{Label, builder::BuildError};
+
+#[test]
+fn build_fixture() {
+    // n^i-o+i=cl/A:2+3+1/B:04-xx_xx/C:13_xx+xx/D:20+1_1/E:xx_xx!xx_xx-xx/F:3_1#0_xx@1_4|1_12/G:3_3%0_xx_1/H:xx_xx/I:4-12@1+2&1-6|1+21/J:2_9/K:2+6-21
+    let (_, expected) = &fixtures()[11];
+    let label = Label::builder()
+        .p1("n")
+        .p2("i")
+        .p3("o")
+        .p4("i")
+        .p5("cl")
+        .a1(2)
+        .a2(3)
+        .a3(1)
+        .b1(4)
+        .c1(13)
+        .d1(20)
+        .d2(1)
+        .d3(1)
+        .f1(3)
+        .f2(1)
+        .f3(false)
+        .f5(1)
+        .f6(4)
+        .f7(1)
+        .f8(12)
+        .g1(3)
+        .g2(3)
+        .g3(false)
+        .g5(true)
+        .i1(4)
+        .i2(12)
+        .i3(1)
+        .i4(2)
+        .i5(1)
+        .i6(6)
+        .i7(1)
+        .i8(21)
+        .j1(2)
+        .j2(9)
+        .k1(2)
+        .k2(6)
+        .k3(21)
+        .build()
+        .unwrap();
+    assert_eq!(&label, expected);
+}
+
+#[test]
+fn defaults() {
+    let label = Label::builder()
+        .p3("sil")
+        .p4("xx")
+        .k1(1)
+        .k2(1)
+        .k3(5)
+        .build()
+        .unwrap();
+    assert_eq!(
+        label.to_string(),
+        "xx^xx-sil+xx=xx/A:xx+xx+xx/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:xx_xx#xx_xx@xx_xx|xx_xx/G:xx_xx%xx_xx_xx/H:xx_xx/I:xx-xx@xx+xx&xx-xx|xx+xx/J:xx_xx/K:1+1-5"
+    );
+}
+
+#[test]
+fn missing() {
+    assert_eq!(Label::builder().build(), Err(BuildError::Missing("K1")));
+
+    let builder = Label::builder().k1(1).k2(1).k3(1);
+    assert_eq!(
+        builder.clone().a1(0).a3(1).build(),
+        Err(BuildError::Missing("A2"))
+    );
+    assert_eq!(
+        builder.clone().e5(true).build(),
+        Err(BuildError::Missing("E1"))
+    );
+    assert_eq!(
+        builder.clone().j2(1).build(),
+        Err(BuildError::Missing("J1"))
+    );
+    assert_eq!(
+        builder
+            .clone()
+            .f1(1)
+            .f2(1)
+            .f3(false)
+            .f5(1)
+            .f6(1)
+            .f7(1)
+            .build(),
+        Err(BuildError::Missing("F8"))
+    );
+    assert!(builder.e1(1).e2(1).e3(false).build().is_ok());
+}
+
+#[test]
+fn inconsistent() {
+    let builder = Label::builder()
+        .a1(-1)
+        .a2(1)
+        .a3(2)
+        .f1(2)
+        .f2(2)
+        .f3(false)
+        .f5(1)
+        .f6(1)
+        .f7(1)
+        .f8(2)
+        .i1(1)
+        .i2(2)
+        .i3(1)
+        .i4(1)
+        .i5(1)
+        .i6(1)
+        .i7(1)
+        .i8(2)
+        .k1(1)
+        .k2(1)
+        .k3(2);
+    assert!(builder.clone().build().is_ok());
+
+    assert_eq!(
+        builder.clone().a3(3).build(),
+        Err(BuildError::Inconsistent {
+            rule: "A2 + A3 - 1 = F1",
+            left: 3,
+            right: 2
+        })
+    );
+    assert_eq!(
+        builder.clone().a1(0).build(),
+        Err(BuildError::Inconsistent {
+            rule: "A1 = A2 - F2",
+            left: 0,
+            right: -1
+        })
+    );
+    assert_eq!(
+        builder.clone().f6(2).build(),
+        Err(BuildError::Inconsistent {
+            rule: "F5 + F6 - 1 = I1",
+            left: 2,
+            right: 1
+        })
+    );
+    assert_eq!(
+        builder.clone().k3(3).build(),
+        Err(BuildError::Inconsistent {
+            rule: "I7 + I8 - 1 = K3",
+            left: 2,
+            right: 3
+        })
+    );
+    assert_eq!(
+        builder.k3(3).build().unwrap_err().to_string(),
+        "Inconsistent fields: I7 + I8 - 1 = K3 (2 != 3)"
+    );
+}
@@ -0,0 +1,55 @@
+mod fixtures;
+use fixtures::fixtures;
+use jlabel::codes::{ConjugationForm, ConjugationType, PartOfSpeech};
+
+#[test]
+fn round_trip() {
+    for pos in PartOfSpeech::ALL {
+        assert_eq!(PartOfSpeech::from_code(pos.code()), Some(*pos));
+        assert_eq!(PartOfSpeech::from_name(pos.japanese()), Some(*pos));
+        assert_eq!(PartOfSpeech::from_name(pos.english()), Some(*pos));
+    }
+    for ctype in ConjugationType::ALL {
+        assert_eq!(ConjugationType::from_code(ctype.code()), Some(*ctype));
+        assert_eq!(ConjugationType::from_name(ctype.japanese()), Some(*ctype));
+    }
+    for cform in ConjugationForm::ALL {
+        assert_eq!(ConjugationForm::from_code(cform.code()), Some(*cform));
+        assert_eq!(ConjugationForm::from_name(cform.english()), Some(*cform));
+    }
+}
+
+#[test]
+fn codes() {
+    assert_eq!(PartOfSpeech::ALL.len(), 24);
+    assert_eq!(PartOfSpeech::from_code(21), None);
+    assert_eq!(PartOfSpeech::from_code(0), None);
+    assert_eq!(
+        ConjugationType::from_code(3),
+        Some(ConjugationType::Ichidan)
+    );
+    assert_eq!(ConjugationType::Ichidan.code(), 2);
+    assert_eq!(ConjugationForm::from_code(7), None);
+    assert_eq!(PartOfSpeech::from_name("名詞"), None);
+    assert_eq!(ConjugationForm::Stem.to_string(), "語幹");
+}
+
+#[test]
+fn word() {
+    // n^i-o+i=cl/A:2+3+1/B:04-xx_xx/C:13_xx+xx/D:20+1_1
+    let (_, label) = &fixtures()[11];
+    let word_prev = label.word_prev.as_ref().unwrap();
+    assert_eq!(word_prev.part_of_speech(), Some(PartOfSpeech::Pronoun));
+    assert_eq!(word_prev.conjugation_type(), None);
+
+    let word_curr = label.word_curr.as_ref().unwrap();
+    assert_eq!(word_curr.part_of_speech(), Some(PartOfSpeech::CaseParticle));
+
+    let word_next = label.word_next.as_ref().unwrap();
+    assert_eq!(word_next.part_of_speech(), Some(PartOfSpeech::MainVerb));
+    assert_eq!(word_next.conjugation_type(), Some(ConjugationType::Godan));
+    assert_eq!(
+        word_next.conjugation_form(),
+        Some(ConjugationForm::Continuative)
+    );
+}
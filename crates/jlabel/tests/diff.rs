@@ -0,0 +1,63 @@
+mod fixtures;
+use fixtures::fixtures;
+use jlabel::{
+    Label,
+    diff::{DiffEntry, FieldChange, diff},
+    field::FieldId,
+};
+
+#[test]
+fn identical() {
+    let labels: Vec<Label> = fixtures().into_iter().map(|(_, label)| label).collect();
+    let diff = diff(&labels, &labels);
+    assert!(diff.is_empty());
+    assert_eq!(diff.entries().len(), labels.len());
+}
+
+#[test]
+fn insert_delete_change() {
+    let old: Vec<Label> = fixtures()
+        .into_iter()
+        .take(11)
+        .map(|(_, label)| label)
+        .collect();
+
+    let mut new = old.clone();
+    // Insert pause after `n`
+    let mut pau = new[0].clone();
+    pau.phoneme.c = Some("pau".to_string());
+    new.insert(5, pau);
+    // Delete `N`
+    new.remove(3);
+    // Change accent
+    new[1].mora.as_mut().unwrap().relative_accent_position = -3;
+    new[1].phoneme.n2 = None;
+
+    let diff = diff(&old, &new);
+    assert!(!diff.is_empty());
+    assert_eq!(
+        diff.entries()[1],
+        DiffEntry::Aligned {
+            old: 1,
+            new: 1,
+            changes: vec![
+                FieldChange {
+                    field: FieldId::P5,
+                    old: Some("N".to_string()),
+                    new: None,
+                },
+                FieldChange {
+                    field: FieldId::A1,
+                    old: Some("-4".to_string()),
+                    new: Some("-3".to_string()),
+                },
+            ],
+        }
+    );
+    assert_eq!(diff.entries()[3], DiffEntry::Deleted { old: 3 });
+    assert_eq!(diff.entries()[5], DiffEntry::Inserted { new: 4 });
+    assert_eq!(
+        diff.to_string(),
+        " sil\n~k\n    P5: N -> xx\n    A1: -4 -> -3\n o\n-N\n n\n+pau\n i\n ch\n i\n w\n a\n sil\n"
+    );
+}
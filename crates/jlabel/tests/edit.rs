@@ -0,0 +1,114 @@
+mod fixtures;
+use fixtures::konnichiwa;
+use jlabel::{
+    Label,
+    edit::{EditError, LabelEditor},
+    kana::from_kana,
+};
+
+fn editor(kana: &str) -> LabelEditor {
+    LabelEditor::new(&from_kana(kana).unwrap()).unwrap()
+}
+
+#[test]
+fn identity() {
+    let labels = konnichiwa();
+    assert_eq!(LabelEditor::new(&labels).unwrap().to_labels(), labels);
+
+    let labels = from_kana("キョ'オワ、イ'イ/テンキデ'ス？、ア").unwrap();
+    let editor = LabelEditor::new(&labels).unwrap();
+    assert_eq!(editor.phrase_count(), 4);
+    assert_eq!(editor.mora_count(2), Ok(5));
+    assert_eq!(editor.to_labels(), labels);
+}
+
+#[test]
+fn keep_words() {
+    let mut editor = LabelEditor::new(&konnichiwa()).unwrap();
+    editor.set_accent_position(0, 1).unwrap();
+    let labels = editor.to_labels();
+    assert_eq!(
+        labels[1].to_string(),
+        "xx^sil-k+o=N/A:0+1+5/B:xx-xx_xx/C:09_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:5_1#0_xx@1_1|1_5/G:xx_xx%xx_xx_xx/H:xx_xx/I:1-5@1+1&1-1|1+5/J:xx_xx/K:1+1-5"
+    );
+}
+
+#[test]
+fn operations() {
+    let base = "コンニチワ'/ゲンキ'デスカ？";
+    type Edit = fn(&mut LabelEditor) -> Result<(), EditError>;
+    let cases: [(Edit, &str); 8] = [
+        (
+            |e| e.set_accent_position(1, 1),
+            "コンニチワ'/ゲ'ンキデスカ？",
+        ),
+        (
+            |e| e.set_accent_position(1, 0),
+            "コンニチワ'/ゲンキデスカ'？",
+        ),
+        (
+            |e| e.set_interrogative(1, false),
+            "コンニチワ'/ゲンキ'デスカ",
+        ),
+        (|e| e.split_phrase(1, 3), "コンニチワ'/ゲンキ'/デスカ'？"),
+        (|e| e.split_phrase(1, 2), "コンニチワ'/ゲン'/キ'デスカ？"),
+        (|e| e.merge_phrases(0), "コンニチワゲンキ'デスカ？"),
+        (|e| e.insert_pause(0), "コンニチワ'、ゲンキ'デスカ？"),
+        (
+            |e| {
+                e.insert_pause(0)?;
+                e.merge_phrases(0)
+            },
+            "コンニチワゲンキ'デスカ？",
+        ),
+    ];
+    for (edit, expected) in cases {
+        let mut editor = editor(base);
+        edit(&mut editor).unwrap();
+        assert_eq!(
+            editor.to_labels(),
+            from_kana(expected).unwrap(),
+            "{expected}"
+        );
+    }
+
+    let mut editor = editor("ア'、イ、ウ");
+    editor.remove_pause(1).unwrap();
+    assert_eq!(editor.to_labels(), from_kana("ア'、イ'/ウ'").unwrap());
+    editor.merge_phrases(0).unwrap();
+    assert_eq!(editor.to_labels(), from_kana("アイ'/ウ'").unwrap());
+}
+
+#[test]
+fn errors() {
+    let mut editor = editor("コンニチワ'/ゲンキ'デスカ？");
+    assert_eq!(
+        editor.set_accent_position(2, 1),
+        Err(EditError::PhraseOutOfRange(2))
+    );
+    assert_eq!(
+        editor.set_accent_position(0, 6),
+        Err(EditError::AccentOutOfRange(6))
+    );
+    assert_eq!(editor.split_phrase(0, 0), Err(EditError::MoraOutOfRange(0)));
+    assert_eq!(editor.split_phrase(0, 5), Err(EditError::MoraOutOfRange(5)));
+    assert_eq!(editor.merge_phrases(1), Err(EditError::PhraseOutOfRange(2)));
+    assert_eq!(editor.insert_pause(1), Err(EditError::PhraseOutOfRange(2)));
+    assert_eq!(editor.remove_pause(0), Err(EditError::NoPause(0)));
+    editor.insert_pause(0).unwrap();
+    assert_eq!(editor.insert_pause(0), Err(EditError::PauseExists(0)));
+
+    let labels = from_kana("ア、イ").unwrap();
+    assert_eq!(LabelEditor::new(&[]), Err(EditError::Malformed(0)));
+    assert_eq!(LabelEditor::new(&labels[1..]), Err(EditError::Malformed(0)));
+    assert_eq!(
+        LabelEditor::new(&labels[..labels.len() - 1]),
+        Err(EditError::Malformed(3))
+    );
+    let doubled: Vec<Label> = [&labels[..3], &labels[2..]].concat();
+    assert_eq!(LabelEditor::new(&doubled), Err(EditError::Malformed(3)));
+    assert_eq!(
+        LabelEditor::new(&[labels[0].clone(), labels[2].clone(), labels[4].clone()]),
+        Err(EditError::Malformed(1))
+    );
+}
@@ -0,0 +1,138 @@
+mod fixtures;
+use fixtures::fixtures;
+use jlabel::{
+    Label,
+    field::{FieldError, FieldId, FieldValue},
+};
+
+#[test]
+fn field_id() {
+    assert_eq!(FieldId::ALL.len(), 50);
+    for field in FieldId::ALL {
+        assert_eq!(field.to_string().parse(), Ok(field));
+    }
+    assert_eq!(
+        "L1".parse::<FieldId>(),
+        Err(FieldError::Unknown("L1".to_string()))
+    );
+}
+
+#[test]
+fn get_set_roundtrip() {
+    let (_, blank) = &fixtures()[0];
+    let mut blank = blank.clone();
+    for field in FieldId::ALL {
+        blank.set_field(field, None).ok();
+    }
+
+    for (source, label) in fixtures() {
+        let mut rebuilt = blank.clone();
+        for (field, value) in label.fields() {
+            rebuilt.set_field(field, value).unwrap();
+        }
+        assert_eq!(rebuilt, label, "{source}");
+    }
+}
+
+#[test]
+fn get_field() {
+    let (_, label) = &fixtures()[1];
+    assert_eq!(
+        label.get_field(FieldId::P3),
+        Some(FieldValue::Phoneme("k".to_string()))
+    );
+    assert_eq!(label.get_field(FieldId::P1), None);
+    assert_eq!(label.get_field(FieldId::A1), Some(FieldValue::Signed(-4)));
+    assert_eq!(label.get_field(FieldId::C1), Some(FieldValue::Unsigned(9)));
+    assert_eq!(label.get_field(FieldId::C2), None);
+    assert_eq!(
+        label.get_field(FieldId::F3),
+        Some(FieldValue::Boolean(false))
+    );
+    assert_eq!(label.get_field(FieldId::F4), None);
+    assert_eq!(label.get_field(FieldId::K3), Some(FieldValue::Unsigned(5)));
+}
+
+#[test]
+fn pause_insertion_as_in_label() {
+    let (source, _) = &fixtures()[1];
+    let label: Label = source
+        .replace("/E:xx_xx!xx_xx-xx/", "/E:5_1!0_xx-1/")
+        .replace("/G:xx_xx%xx_xx_xx/", "/G:2_1%0_xx_0/")
+        .parse()
+        .unwrap();
+    assert_eq!(
+        label
+            .accent_phrase_prev
+            .as_ref()
+            .unwrap()
+            .is_pause_insertion,
+        Some(false)
+    );
+
+    let e5 = label.get_field(FieldId::E5).unwrap();
+    assert_eq!(e5, FieldValue::Boolean(true));
+    assert_eq!(e5.to_string(), "1");
+    assert_eq!(label.get_field(FieldId::G5).unwrap().to_string(), "0");
+
+    let fields: Vec<_> = label
+        .fields()
+        .filter(|(field, _)| matches!(field, FieldId::E5 | FieldId::G5))
+        .map(|(field, value)| format!("{field}:{}", value.unwrap()))
+        .collect();
+    assert_eq!(fields, ["E5:1", "G5:0"]);
+}
+
+#[test]
+fn set_field() {
+    let (_, original) = &fixtures()[1];
+    let mut label = original.clone();
+
+    label
+        .set_field(FieldId::H1, Some(FieldValue::Unsigned(2)))
+        .unwrap();
+    assert!(label.to_string().contains("/H:2_0/"));
+    label.set_field(FieldId::H2, None).unwrap();
+    assert_eq!(label.breath_group_prev, None);
+
+    label
+        .set_field(FieldId::E5, Some(FieldValue::Boolean(true)))
+        .unwrap();
+    assert!(label.to_string().contains("/E:0_0!0_xx-1/"));
+    assert_eq!(
+        label
+            .accent_phrase_prev
+            .as_ref()
+            .unwrap()
+            .is_pause_insertion,
+        Some(false)
+    );
+
+    label
+        .set_field(FieldId::B2, Some(FieldValue::Unsigned(1)))
+        .unwrap();
+    assert!(label.to_string().contains("/B:xx-1_xx/"));
+    label.set_field(FieldId::B2, None).unwrap();
+    assert_eq!(label.word_prev, None);
+
+    label.set_field(FieldId::C1, None).unwrap();
+    assert_eq!(label.word_curr, None);
+
+    let before = label.clone();
+    assert_eq!(
+        label.set_field(FieldId::A1, Some(FieldValue::Unsigned(1))),
+        Err(FieldError::TypeMismatch(FieldId::A1, "signed integer"))
+    );
+    assert_eq!(
+        label.set_field(FieldId::F4, Some(FieldValue::Unsigned(1))),
+        Err(FieldError::NotUndefined(FieldId::F4))
+    );
+    assert_eq!(
+        label.set_field(FieldId::K1, None),
+        Err(FieldError::Required(FieldId::K1))
+    );
+    assert_eq!(label, before);
+
+    let label: Label = label.to_string().parse().unwrap();
+    assert_eq!(label.word_curr, None);
+}
@@ -616,3 +616,13 @@ pub fn fixtures() -> [(&'static str, Label); 12] {
         ),
     ]
 }
+
+/// The labels of こんにちは, the first 11 of [`fixtures`].
+#[allow(dead_code)]
+pub fn konnichiwa() -> Vec<Label> {
+    fixtures()
+        .into_iter()
+        .take(11)
+        .map(|(_, label)| label)
+        .collect()
+}
@@ -0,0 +1,50 @@
+mod fixtures;
+use fixtures::fixtures;
+use jlabel::{Label, intern::PhonemeTable};
+
+fn labels() -> Vec<Label> {
+    fixtures().into_iter().map(|(_, label)| label).collect()
+}
+
+#[test]
+fn intern_resolve() {
+    let labels = labels();
+    let mut table = PhonemeTable::new();
+    let interned = table.intern_labels(labels.clone());
+
+    assert_eq!(interned.len(), labels.len());
+    for (label, interned) in labels.iter().zip(&interned) {
+        assert_eq!(&table.resolve_label(interned), label);
+        assert_eq!(
+            interned.phoneme.c.and_then(|id| table.resolve(id)),
+            label.phoneme.c.as_deref()
+        );
+    }
+    for window in interned[..11].windows(2) {
+        assert_eq!(window[0].phoneme.c, window[1].phoneme.p1);
+        assert_eq!(window[0].phoneme.n1, window[1].phoneme.c);
+    }
+
+    // The table is shared by another sequence.
+    let len = table.len();
+    let again = table.intern_labels(labels);
+    assert_eq!(again, interned);
+    assert_eq!(table.len(), len);
+}
+
+#[test]
+fn table() {
+    let mut table = PhonemeTable::new();
+    assert!(table.is_empty());
+    let a = table.intern("a");
+    let b = table.intern("b");
+    assert_ne!(a, b);
+    assert_eq!(table.intern("a"), a);
+    assert_eq!(table.get("b"), Some(b));
+    assert_eq!(table.get("c"), None);
+    assert_eq!((a.index(), b.index()), (0, 1));
+    assert_eq!(table.iter().collect::<Vec<_>>(), [(a, "a"), (b, "b")]);
+
+    let other = PhonemeTable::new();
+    assert_eq!(other.resolve(a), None);
+}
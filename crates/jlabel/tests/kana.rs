@@ -0,0 +1,142 @@
+mod fixtures;
+use fixtures::konnichiwa;
+use jlabel::{
+    Label,
+    kana::{KanaError, from_kana, to_kana},
+};
+
+/// こんにちは twice as the second and third accent phrases, joined by `middle`.
+fn twice(middle: &[Label]) -> Vec<Label> {
+    let labels = konnichiwa();
+    let mut second = labels[1..].to_vec();
+    for label in &mut second {
+        if let Some(ap) = &mut label.accent_phrase_curr {
+            ap.accent_phrase_position_forward = 2;
+            ap.is_interrogative = true;
+        }
+    }
+    labels[..10]
+        .iter()
+        .chain(middle)
+        .chain(&second)
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn konnichiwa_kana() {
+    assert_eq!(to_kana(&konnichiwa()), "コンニチワ'");
+    assert_eq!(to_kana(&[]), "");
+}
+
+#[test]
+fn accent_nucleus() {
+    let mut labels = konnichiwa();
+    for label in &mut labels {
+        if let Some(ap) = &mut label.accent_phrase_curr {
+            ap.accent_position = 1;
+        }
+    }
+    assert_eq!(to_kana(&labels), "コ'ンニチワ");
+
+    for label in &mut labels {
+        if let Some(ap) = &mut label.accent_phrase_curr {
+            ap.accent_position = 0;
+        }
+    }
+    assert_eq!(to_kana(&labels), "コンニチワ");
+}
+
+#[test]
+fn separators() {
+    assert_eq!(to_kana(&twice(&[])), "コンニチワ'/コンニチワ'？");
+
+    let mut pau = konnichiwa()[0].clone();
+    pau.phoneme.c = Some("pau".to_string());
+    assert_eq!(to_kana(&twice(&[pau])), "コンニチワ'、コンニチワ'？");
+}
+
+#[test]
+fn devoiced() {
+    let mut labels = konnichiwa();
+    labels[7].phoneme.c = Some("I".to_string());
+    assert_eq!(to_kana(&labels), "コンニチワ'");
+}
+
+#[test]
+fn from_kana_konnichiwa() {
+    let mut expected = konnichiwa();
+    for label in &mut expected {
+        label.word_curr = None;
+    }
+    assert_eq!(from_kana("コンニチワ").unwrap(), expected);
+    assert_eq!(from_kana("コンニチワ'").unwrap(), expected);
+}
+
+#[test]
+fn from_kana_round_trip() {
+    for kana in [
+        "コンニチワ'/ゲンキ'デスカ？",
+        "キョ'オワ、イ'イ/テンキデ'ス",
+        "ア'/イ'？、ウ'？/ン'",
+        "ファ'ッション/ヴァ'イオリン",
+    ] {
+        let labels = from_kana(kana).unwrap();
+        assert_eq!(to_kana(&labels), kana);
+        let reparsed: Vec<Label> = labels
+            .iter()
+            .map(|label| label.to_string().parse().unwrap())
+            .collect();
+        assert_eq!(reparsed, labels);
+    }
+}
+
+#[test]
+fn from_kana_long_vowel() {
+    assert_eq!(
+        from_kana("キョ'ーワ").unwrap(),
+        from_kana("キョ'オワ").unwrap()
+    );
+}
+
+#[test]
+fn from_kana_pause() {
+    let labels = from_kana("ア'、イ'").unwrap();
+    let phonemes: Vec<_> = labels
+        .iter()
+        .map(|label| label.phoneme.c.as_deref().unwrap())
+        .collect();
+    assert_eq!(phonemes, ["sil", "a", "pau", "i", "sil"]);
+    assert_eq!(
+        labels[1].to_string(),
+        "xx^sil-a+pau=i/A:0+1+1/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:xx_xx!xx_xx-xx/F:1_1#0_xx@1_1|1_1/G:1_1%0_xx_0/H:xx_xx/I:1-1@1+2&1-2|1+2/J:1_1/K:2+2-2"
+    );
+    assert_eq!(
+        labels[2].to_string(),
+        "sil^a-pau+i=sil/A:xx+xx+xx/B:xx-xx_xx/C:xx_xx+xx/D:xx+xx_xx/E:1_1!0_xx-xx/F:xx_xx#xx_xx@xx_xx|xx_xx/G:1_1%0_xx_xx/H:1_1/I:xx-xx@xx+xx&xx-xx|xx+xx/J:1_1/K:2+2-2"
+    );
+}
+
+#[test]
+fn from_kana_error() {
+    assert_eq!(from_kana(""), Err(KanaError::EmptyAccentPhrase(0)));
+    assert_eq!(from_kana("ア//イ"), Err(KanaError::EmptyAccentPhrase(4)));
+    assert_eq!(from_kana("ア'イ'"), Err(KanaError::MultipleAccentNuclei(7)));
+    assert_eq!(
+        from_kana("'ア"),
+        Err(KanaError::UnexpectedCharacter('\'', 0))
+    );
+    assert_eq!(
+        from_kana("ア？イ"),
+        Err(KanaError::UnexpectedCharacter('イ', 6))
+    );
+    assert_eq!(
+        from_kana("ーア"),
+        Err(KanaError::UnexpectedCharacter('ー', 0))
+    );
+    assert_eq!(
+        from_kana("アx"),
+        Err(KanaError::UnexpectedCharacter('x', 3))
+    );
+    assert_eq!(from_kana(&"ア".repeat(256)), Err(KanaError::TooManyMoras));
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "rayon")]
+
+mod fixtures;
+use fixtures::fixtures;
+use jlabel::{
+    parallel::{read_dir, read_files},
+    table::TableError,
+};
+
+#[test]
+fn read_dir_in_order() {
+    let dir = std::env::temp_dir().join(format!("jlabel-parallel-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("sub.lab")).unwrap();
+
+    let fixtures = fixtures();
+    let text: String = fixtures[..11]
+        .iter()
+        .map(|(source, _)| format!("{source}\n"))
+        .collect();
+    std::fs::write(dir.join("b.lab"), &text).unwrap();
+    std::fs::write(
+        dir.join("a.lab"),
+        format!("# comment\n{}\n", fixtures[11].0),
+    )
+    .unwrap();
+    std::fs::write(dir.join("c.lab"), format!("{text}broken\n")).unwrap();
+    std::fs::write(dir.join("d.txt"), "ignored").unwrap();
+
+    let results = read_dir(&dir, "lab").unwrap();
+    let names: Vec<_> = results
+        .iter()
+        .map(|(path, _)| path.file_name().unwrap().to_str().unwrap())
+        .collect();
+    assert_eq!(names, ["a.lab", "b.lab", "c.lab"]);
+    assert_eq!(results[0].1.as_ref().unwrap(), &[fixtures[11].1.clone()]);
+    assert_eq!(results[1].1.as_ref().unwrap().len(), 11);
+    assert!(matches!(results[2].1, Err(TableError::Parse(12, _))));
+
+    let results = read_files(&[dir.join("missing.lab"), dir.join("a.lab")]);
+    assert!(matches!(results[0], Err(TableError::Io(_))));
+    assert!(results[1].is_ok());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,55 @@
+mod fixtures;
+use fixtures::konnichiwa;
+use jlabel::{
+    Label,
+    pitch::{Pitch, pitch},
+};
+
+/// Changes the accent position of こんにちは.
+fn with_accent(accent_position: u8) -> Vec<Label> {
+    let mut labels = konnichiwa();
+    for label in &mut labels {
+        if let (Some(mora), Some(ap)) = (&mut label.mora, &mut label.accent_phrase_curr) {
+            mora.relative_accent_position = mora.position_forward as i8 - accent_position as i8;
+            ap.accent_position = accent_position;
+        }
+    }
+    labels
+}
+
+#[test]
+fn heiban() {
+    let labels = konnichiwa();
+    let phrases = pitch(&labels);
+    assert_eq!(phrases.len(), 1);
+
+    let phrase = &phrases[0];
+    assert!(phrase.is_heiban());
+    assert_eq!(phrase.labels, 1..10);
+    assert_eq!(
+        phrase
+            .moras
+            .iter()
+            .map(|mora| mora.labels.clone())
+            .collect::<Vec<_>>(),
+        [1..3, 3..4, 4..6, 6..8, 8..10]
+    );
+    assert_eq!(phrase.moras[0].pitch, Pitch::Low);
+    assert_eq!(phrase.to_string(), "LHHHH");
+}
+
+#[test]
+fn accent_types() {
+    for (accent_position, expected) in [(1, "HLLLL"), (2, "LHLLL"), (4, "LHHHL"), (0, "LHHHH")] {
+        let phrases = pitch(&with_accent(accent_position));
+        assert_eq!(phrases[0].is_heiban(), accent_position == 0);
+        assert_eq!(phrases[0].to_string(), expected, "{accent_position}");
+    }
+}
+
+#[test]
+fn no_mora() {
+    let labels = konnichiwa();
+    assert_eq!(pitch(&labels[..1]), []);
+    assert_eq!(pitch(&[]), []);
+}
@@ -0,0 +1,12 @@
+#![cfg(feature = "proptest")]
+
+use jlabel::Label;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn round_trip(label: Label) {
+        let serialized = label.to_string();
+        prop_assert_eq!(serialized.parse::<Label>().unwrap(), label, "{}", serialized);
+    }
+}
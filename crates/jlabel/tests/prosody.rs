@@ -0,0 +1,76 @@
+mod fixtures;
+use fixtures::konnichiwa;
+use jlabel::{Label, kana::from_kana, prosody::prosody};
+
+/// Checks the label dumps of Open JTalk in `tests/prosody/*.lab`
+/// against the symbols of pyopenjtalk in `*.txt` next to them.
+///
+/// To add a case, save `pyopenjtalk.extract_fullcontext(text)` one label per line as `<name>.lab`,
+/// and the prosody symbols (`pp_symbols` of ESPnet) of the labels joined by space as `<name>.txt`.
+#[test]
+fn reference() {
+    let mut count = 0;
+    for entry in std::fs::read_dir("tests/prosody").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|ext| ext != "lab") {
+            continue;
+        }
+        let labels: Vec<Label> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        let expected = std::fs::read_to_string(path.with_extension("txt")).unwrap();
+        assert_eq!(
+            prosody(&labels, true).join(" "),
+            expected.trim_end(),
+            "{}",
+            path.display()
+        );
+        count += 1;
+    }
+    assert!(count > 0);
+}
+
+/// The labels generated by [`from_kana`] are consistent with [`prosody`].
+///
+/// This is not a reference test, as the labels are not from Open JTalk.
+#[test]
+fn from_kana_round_trip() {
+    for (kana, expected) in [
+        ("コンニチワ", "^ k o [ N n i ch i w a $"),
+        ("ゲ'ンキデスカ？", "^ g e ] N k i d e s u k a ?"),
+        (
+            "コンニチワ/ゲ'ンキ",
+            "^ k o [ N n i ch i w a # g e ] N k i $",
+        ),
+        (
+            "コンニチワ、ゲ'ンキ",
+            "^ k o [ N n i ch i w a _ g e ] N k i $",
+        ),
+        ("ア'メ", "^ a ] m e $"),
+        ("ア", "^ a $"),
+        ("アメ'", "^ a [ m e $"),
+        ("ニ'ッポン/イチ'", "^ n i ] cl p o N # i [ ch i $"),
+        (
+            "キョ'オワ/イ'イ/テンキデ'ス",
+            "^ ky o ] o w a # i ] i # t e [ N k i d e ] s u $",
+        ),
+    ] {
+        let labels = from_kana(kana).unwrap();
+        assert_eq!(prosody(&labels, true).join(" "), expected, "{kana}");
+    }
+}
+
+#[test]
+fn unvoiced_vowels() {
+    let mut labels = konnichiwa();
+    labels[7].phoneme.c = Some("I".to_string());
+    assert_eq!(prosody(&labels, true)[8], "i");
+    assert_eq!(prosody(&labels, false)[8], "I");
+}
+
+#[test]
+fn empty() {
+    assert_eq!(prosody(&[], true), [] as [&str; 0]);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "std")]
+
+mod fixtures;
+use fixtures::fixtures;
+use jlabel::{reader::LabelReader, table::TableError};
+
+#[test]
+fn read_all() {
+    let text: String = fixtures()
+        .iter()
+        .map(|(source, _)| format!("{source}\n"))
+        .collect();
+    let labels: Vec<_> = LabelReader::new(text.as_bytes())
+        .map(|result| result.unwrap())
+        .collect();
+    let expected: Vec<_> = fixtures()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (_, label))| (index + 1, label))
+        .collect();
+    assert_eq!(labels, expected);
+}
+
+#[test]
+fn timings_and_recovery() {
+    let fixtures = fixtures();
+    let text = format!(
+        "# comment\n\n0 100 {}\r\n100 200 {}\nbroken\n1 2\nx 300 {}\n  \n300 400 {}",
+        fixtures[0].0, fixtures[1].0, fixtures[2].0, fixtures[3].0
+    );
+    let mut reader = LabelReader::new(text.as_bytes());
+
+    let (line, row) = reader.next_row().unwrap().unwrap();
+    assert_eq!((line, row.start, row.end), (3, Some(0), Some(100)));
+    assert_eq!(row.label, fixtures[0].1);
+    let (line, label) = reader.next().unwrap().unwrap();
+    assert_eq!((line, label), (4, fixtures[1].1.clone()));
+    assert!(matches!(reader.next(), Some(Err(TableError::Parse(5, _)))));
+    assert!(matches!(
+        reader.next(),
+        Some(Err(TableError::InvalidHtk(6)))
+    ));
+    assert!(matches!(
+        reader.next(),
+        Some(Err(TableError::InvalidTime(7)))
+    ));
+    let (line, row) = reader.next_row().unwrap().unwrap();
+    assert_eq!((line, row.start, row.end), (9, Some(300), Some(400)));
+    assert!(reader.next().is_none());
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn invalid_utf8() {
+    let mut bytes = b"\xff\xfe\n".to_vec();
+    bytes.extend(fixtures()[0].0.as_bytes());
+    let mut reader = LabelReader::new(bytes.as_slice());
+    assert!(matches!(reader.next(), Some(Err(TableError::Io(_)))));
+    let (line, label) = reader.next().unwrap().unwrap();
+    assert_eq!((line, label), (2, fixtures()[0].1.clone()));
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "serde")]
+
+mod fixtures;
+use fixtures::fixtures;
+use jlabel::Label;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct AsString {
+    #[serde(with = "jlabel::serde::as_string")]
+    label: Label,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct AsMap {
+    #[serde(with = "jlabel::serde::as_map")]
+    label: Label,
+}
+
+#[test]
+fn as_string() {
+    for (source, label) in fixtures() {
+        let value = AsString { label };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, format!("{{\"label\":\"{source}\"}}"));
+        assert_eq!(serde_json::from_str::<AsString>(&json).unwrap(), value);
+    }
+
+    assert!(serde_json::from_str::<AsString>(r#"{"label":"broken"}"#).is_err());
+    assert!(serde_json::from_str::<AsString>(r#"{"label":1}"#).is_err());
+}
+
+#[test]
+fn as_map() {
+    for (source, label) in fixtures() {
+        let value = AsMap { label };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AsMap>(&json).unwrap(),
+            value,
+            "{source}"
+        );
+    }
+
+    let (_, label) = &fixtures()[1];
+    let json = serde_json::to_value(AsMap {
+        label: label.clone(),
+    })
+    .unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "label": {
+                "P2": "sil", "P3": "k", "P4": "o", "P5": "N",
+                "A1": -4, "A2": 1, "A3": 5,
+                "C1": 9,
+                "F1": 5, "F2": 5, "F3": 0, "F5": 1, "F6": 1, "F7": 1, "F8": 5,
+                "I1": 1, "I2": 5, "I3": 1, "I4": 1, "I5": 1, "I6": 1, "I7": 1, "I8": 5,
+                "K1": 1, "K2": 1, "K3": 5,
+            }
+        })
+    );
+}
+
+#[test]
+fn as_map_errors() {
+    let parse = |map: &str| serde_json::from_str::<AsMap>(&format!("{{\"label\":{map}}}"));
+
+    let label = parse(r#"{"P3":"a","E5":1,"B2":1,"K1":1,"K2":1,"K3":1}"#)
+        .unwrap()
+        .label;
+    assert_eq!(label.phoneme.c.as_deref(), Some("a"));
+    assert_eq!(
+        label.accent_phrase_prev.unwrap().is_pause_insertion,
+        Some(false)
+    );
+    assert_eq!(label.word_prev.unwrap().ctype, Some(1));
+
+    assert!(parse(r#"{"P3":"a"}"#).is_err());
+    assert!(parse(r#"{"L1":1,"K1":1,"K2":1,"K3":1}"#).is_err());
+    assert!(parse(r#"{"A1":"a","K1":1,"K2":1,"K3":1}"#).is_err());
+    assert!(parse(r#"{"A2":-1,"K1":1,"K2":1,"K3":1}"#).is_err());
+    assert!(parse(r#"{"A1":128,"K1":1,"K2":1,"K3":1}"#).is_err());
+    assert!(parse(r#"{"F3":2,"K1":1,"K2":1,"K3":1}"#).is_err());
+    assert!(parse(r#"{"F3":true,"K1":1,"K2":1,"K3":1}"#).is_err());
+    assert!(parse(r#"{"F4":1,"K1":1,"K2":1,"K3":1}"#).is_err());
+}
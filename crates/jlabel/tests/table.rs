@@ -0,0 +1,133 @@
+#![cfg(feature = "std")]
+
+mod fixtures;
+use fixtures::fixtures;
+use jlabel::table::{
+    Delimiter, Row, TableError, TableOptions, read_table, rows_from_htk, write_table,
+};
+
+fn rows() -> Vec<Row> {
+    fixtures()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (_, label))| Row {
+            file_id: Some(if index < 11 { "a,1" } else { "b\"2" }.to_string()),
+            start: Some(index as u64 * 500000),
+            end: (index < 11).then_some((index as u64 + 1) * 500000),
+            label,
+        })
+        .collect()
+}
+
+#[test]
+fn roundtrip() {
+    for delimiter in [Delimiter::Comma, Delimiter::Tab] {
+        let options = TableOptions {
+            delimiter,
+            file_id: true,
+            timings: true,
+        };
+        let mut table = Vec::new();
+        write_table(&mut table, &rows(), &options).unwrap();
+        assert_eq!(read_table(table.as_slice(), delimiter).unwrap(), rows());
+    }
+
+    let labels: Vec<Row> = fixtures()
+        .into_iter()
+        .map(|(_, label)| label.into())
+        .collect();
+    let mut table = Vec::new();
+    write_table(&mut table, &labels, &TableOptions::default()).unwrap();
+    assert_eq!(
+        read_table(table.as_slice(), Delimiter::Comma).unwrap(),
+        labels
+    );
+}
+
+#[test]
+fn cells() {
+    let mut table = Vec::new();
+    let options = TableOptions {
+        delimiter: Delimiter::Tab,
+        file_id: true,
+        timings: false,
+    };
+    write_table(&mut table, &rows()[1..2], &options).unwrap();
+    let table = String::from_utf8(table).unwrap();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("file_id\tP1\tP2\tP3\t"));
+    assert!(lines[0].ends_with("\tK1\tK2\tK3"));
+    assert!(lines[1].starts_with("a,1\t\tsil\tk\to\tN\t-4\t1\t5\t\t\t\t09\t\t\t"));
+    assert!(lines[1].ends_with("\t1\t1\t5"));
+
+    let mut csv = Vec::new();
+    write_table(
+        &mut csv,
+        &rows()[11..],
+        &TableOptions {
+            file_id: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(String::from_utf8(csv).unwrap().contains("\n\"b\"\"2\","));
+}
+
+#[test]
+fn htk() {
+    let text: String = fixtures()
+        .iter()
+        .enumerate()
+        .map(|(index, (source, _))| format!("{} {} {source}\n", index * 10, index * 10 + 10))
+        .collect();
+    let rows = rows_from_htk(Some("f"), &text).unwrap();
+    assert_eq!(rows.len(), 12);
+    assert_eq!(rows[3].start, Some(30));
+    assert_eq!(rows[3].end, Some(40));
+    assert_eq!(rows[3].file_id.as_deref(), Some("f"));
+    assert_eq!(rows[3].label, fixtures()[3].1);
+
+    let rows = rows_from_htk(None, fixtures()[0].0).unwrap();
+    assert_eq!(rows, vec![Row::from(fixtures()[0].1.clone())]);
+
+    assert!(matches!(
+        rows_from_htk(None, "0 label"),
+        Err(TableError::InvalidHtk(1))
+    ));
+    assert!(matches!(
+        rows_from_htk(None, "\n0 x label"),
+        Err(TableError::InvalidTime(2))
+    ));
+}
+
+#[test]
+fn errors() {
+    let mut table = Vec::new();
+    write_table(&mut table, &rows()[..1], &TableOptions::default()).unwrap();
+    let table = String::from_utf8(table).unwrap();
+
+    assert!(matches!(
+        read_table("".as_bytes(), Delimiter::Comma),
+        Err(TableError::NoHeader)
+    ));
+    assert!(matches!(
+        read_table(table.replacen("P1", "X1", 1).as_bytes(), Delimiter::Comma),
+        Err(TableError::UnknownColumn(column)) if column == "X1"
+    ));
+    assert!(matches!(
+        read_table(table.replacen("P1,", "", 1).as_bytes(), Delimiter::Comma),
+        Err(TableError::MissingColumn(jlabel::field::FieldId::P1))
+    ));
+    assert!(matches!(
+        read_table(format!("{table},").as_bytes(), Delimiter::Comma),
+        Err(TableError::CellCount(3))
+    ));
+    assert!(matches!(
+        read_table(
+            table.replace(",1,1,5", ",1,1,a").as_bytes(),
+            Delimiter::Comma
+        ),
+        Err(TableError::Parse(2, _))
+    ));
+}